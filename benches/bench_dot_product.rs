@@ -44,7 +44,7 @@ impl std::fmt::Display for NumList {
 }
 
 
-//// This is a benchmark for the NVector operation: Dot product
+/// This is a benchmark for the NVector operation: Dot product
 ///
 /// The primary objective is to test the operation across NVecs of a specified set of sizes and numerical types
 /// To allow us to do this, we can use macros to programmatically generate Criterion tests for us according to our parameters
@@ -67,8 +67,6 @@ impl std::fmt::Display for NumList {
 /// As the iterate_types macro is called, it is passed a NumList - this is a wrapper struct (with a Display implementation) for the randomly generated values of the components of the NVecs for that run
 /// This generates the criterion objects, then they are benchmarked together afterwards
 /// 
-
-
 fn bench_addition(c: &mut Criterion) {
     let mut group: BenchmarkGroup<'_, measurement::WallTime> = c.benchmark_group("Dot product");
 
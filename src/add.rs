@@ -21,6 +21,18 @@ where
     }
 }
 
+// Same as +, but an Err instead of wrapping (or panicking in debug) on integer overflow, or of an inf/NaN float
+impl<T: Numerical, const N: usize> NVec<T, N> {
+    pub fn try_add<U, V>(self, rhs: NVec<U, N>) -> Result<NVec<V, N>, NVecError>
+    where
+        T: AsPrimitive<V> + Promote<U, Output = V>,
+        U: Numerical + AsPrimitive<V>,
+        V: CheckedNumerical + 'static,
+    {
+        error::try_components(|i| self.components[i].as_().try_add(rhs.components[i].as_(), i))
+    }
+}
+
 
 
 
@@ -31,6 +43,20 @@ where
 mod tests {
     use super::*;
 
+    #[test]
+    fn try_add_test() {
+        println!("\n\nTesting for checked addition\n\n");
+        assert_eq!(NVec::new([1u8, 2, 3]).try_add(NVec::new([4u8, 5, 6])), Ok(NVec::new([5u8, 7, 9])));
+        assert_eq!(NVec::new([1u8, 250, 3]).try_add(NVec::new([4u8, 6, 253])), Err(NVecError::Overflow { index: 1 }));
+        assert_eq!(NVec::new([i32::MIN, 0]).try_add(NVec::new([-1i32, 0])), Err::<NVec<i32, 2>, _>(NVecError::Overflow { index: 0 }));
+
+        // promotes first, so u8 + u16 only overflows if the u16 does
+        assert_eq!(NVec::new([255u8]).try_add(NVec::new([1u16])), Ok(NVec::new([256u16])));
+
+        assert_eq!(NVec::new([1.0, f64::MAX]).try_add(NVec::new([1.0, f64::MAX])), Err::<NVec<f64, 2>, _>(NVecError::NonFinite { index: 1 }));
+        assert_eq!(NVec::new([f32::NAN]).try_add(NVec::new([1.0f32])), Err::<NVec<f32, 1>, _>(NVecError::NonFinite { index: 0 }));
+    }
+
     #[test]
     fn same_type_test() {
         //declare all the operations, then call the tests to be assembled below
@@ -48,7 +74,7 @@ mod tests {
     fn casting_test(){
         println!("\n\nTesting for addition casting\n\n");
         let a: NVec<f64, 3> = NVec {
-            components: [1.01 as f64, -2.65 as f64, 3.4 as f64],
+            components: [1.01_f64, -2.65_f64, 3.4_f64],
         };
         let b: NVec<i32, 3> = NVec {
             components: [4, -5, 200],
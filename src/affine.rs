@@ -0,0 +1,378 @@
+use super::*;
+use num_traits::Float;
+
+// Homogeneous coordinates and affine transforms
+// An affine transform is a linear part (an N x N matrix, row major) followed by a translation
+// Points get both, vectors (directions, displacements) only get the linear part
+// Perspective isn't affine, so projections come back as (N + 1) x (N + 1) homogeneous matrices instead
+
+
+
+// Homogeneous conversions //
+
+// N + 1 can't be written with stable const generics, so these are generated for the common sizes
+macro_rules! homogeneous {
+    ($(($n:expr, $m:expr)), *) => {
+        $(
+            impl<T: Numerical> NVec<T, $n> {
+                // adds w as a new last component
+                pub fn extend(self, w: T) -> NVec<T, $m> {
+                    NVec::new(core::array::from_fn(|i| if i < $n { self.components[i] } else { w }))
+                }
+            }
+
+            impl<T: Numerical> NVec<T, $m> {
+                // drops the last component
+                pub fn truncate(self) -> NVec<T, $n> {
+                    NVec::new(core::array::from_fn(|i| self.components[i]))
+                }
+            }
+
+            impl<T: Numerical + num_traits::Zero> NVec<T, $m> {
+                // divides through by the last component (w) then drops it
+                // None when w is 0 - that's a direction (a point at infinity) with no position to give back,
+                // and dividing would panic for integers or give inf/NaN for floats
+                pub fn from_homogeneous(self) -> Option<NVec<T, $n>> {
+                    let w = self.components[$n];
+                    if w.is_zero() {
+                        return None;
+                    }
+                    Some(NVec::new(core::array::from_fn(|i| self.components[i] / w)))
+                }
+            }
+
+            impl<T: Float + Numerical> NVec<T, $n> {
+                // a point has w = 1 so translations apply to it
+                pub fn to_homogeneous_point(self) -> NVec<T, $m> {
+                    self.extend(T::one())
+                }
+
+                // a vector has w = 0 so translations don't
+                pub fn to_homogeneous_vector(self) -> NVec<T, $m> {
+                    self.extend(T::zero())
+                }
+            }
+        )*
+    };
+}
+
+homogeneous!((1, 2), (2, 3), (3, 4), (4, 5));
+
+
+
+// Affine transforms //
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Affine<T: Numerical, const N: usize> {
+    pub linear: [[T; N]; N],
+    pub translation: NVec<T, N>,
+}
+
+impl<T: Float + Numerical, const N: usize> Affine<T, N> {
+    pub fn new(linear: [[T; N]; N], translation: NVec<T, N>) -> Self {
+        Affine { linear, translation }
+    }
+
+    pub fn identity() -> Self {
+        Affine::from_linear(core::array::from_fn(|i| core::array::from_fn(|j| if i == j { T::one() } else { T::zero() })))
+    }
+
+    pub fn from_linear(linear: [[T; N]; N]) -> Self {
+        Affine::new(linear, NVec::new([T::zero(); N]))
+    }
+
+    pub fn translation(offset: NVec<T, N>) -> Self {
+        Affine::new(Affine::identity().linear, offset)
+    }
+
+    // scales each axis by the matching component of factors
+    pub fn scale(factors: NVec<T, N>) -> Self {
+        Affine::from_linear(core::array::from_fn(|i| core::array::from_fn(|j| if i == j { factors.components[i] } else { T::zero() })))
+    }
+
+    pub fn uniform_scale(factor: T) -> Self {
+        Affine::scale(NVec::new([factor; N]))
+    }
+
+    // axis i gets factor times axis j added to it, e.g. shear(0, 1, k) maps (x, y) to (x + k y, y)
+    pub fn shear(i: usize, j: usize, factor: T) -> Self {
+        assert!(i < N && j < N && i != j, "Shear needs two different axes within the dimension");
+        let mut a = Affine::identity();
+        a.linear[i][j] = factor;
+        a
+    }
+
+    // rotation by angle radians in the plane of axes i and j, turning i towards j
+    // for N = 2 that's rotation_in_plane(0, 1, angle), the usual anticlockwise rotation
+    pub fn rotation_in_plane(i: usize, j: usize, angle: T) -> Self {
+        assert!(i < N && j < N && i != j, "Rotation needs two different axes within the dimension");
+        let (s, c) = angle.sin_cos();
+        let mut a = Affine::identity();
+        a.linear[i][i] = c;
+        a.linear[i][j] = -s;
+        a.linear[j][i] = s;
+        a.linear[j][j] = c;
+        a
+    }
+
+    pub fn transform_point(&self, p: NVec<T, N>) -> NVec<T, N> {
+        let l = self.transform_vector(p);
+        NVec::new(core::array::from_fn(|i| l.components[i] + self.translation.components[i]))
+    }
+
+    // directions and displacements ignore the translation
+    pub fn transform_vector(&self, v: NVec<T, N>) -> NVec<T, N> {
+        NVec::new(core::array::from_fn(|i| {
+            (0..N).fold(T::zero(), |acc, j| acc + self.linear[i][j] * v.components[j])
+        }))
+    }
+
+    // None if the linear part is singular (squashes space flat, so can't be undone)
+    pub fn inverse(&self) -> Option<Self> {
+        self.try_inverse().ok()
+    }
+
+    // inverse with Err(NVecError::Singular) rather than None
+    pub fn try_inverse(&self) -> Result<Self, NVecError> {
+        // Gauss-Jordan with partial pivoting on [A | I]
+        let mut a = self.linear;
+        let mut inv = Affine::<T, N>::identity().linear;
+
+        // "too small to pivot on" is relative to the size of the matrix, so uniformly tiny (or huge) scales still invert
+        // anything smaller is left over rounding from eliminating a row that was really a combination of the others
+        let size = a.iter().flatten().fold(T::zero(), |m, x| m.max(x.abs()));
+        let threshold = size * T::epsilon() * T::from(N).unwrap_or_else(T::one);
+
+        for col in 0..N {
+            let pivot = (col..N).max_by(|&r1, &r2| a[r1][col].abs().partial_cmp(&a[r2][col].abs()).unwrap_or(std::cmp::Ordering::Equal)).ok_or(NVecError::Singular)?;
+            if a[pivot][col].abs() <= threshold || a[pivot][col] == T::zero() {
+                return Err(NVecError::Singular);
+            }
+            a.swap(col, pivot);
+            inv.swap(col, pivot);
+
+            let p = a[col][col];
+            for k in 0..N {
+                a[col][k] = a[col][k] / p;
+                inv[col][k] = inv[col][k] / p;
+            }
+
+            for row in 0..N {
+                if row != col {
+                    let f = a[row][col];
+                    for k in 0..N {
+                        a[row][k] = a[row][k] - f * a[col][k];
+                        inv[row][k] = inv[row][k] - f * inv[col][k];
+                    }
+                }
+            }
+        }
+
+        // x = A^-1 (y - t) = A^-1 y - A^-1 t
+        let linear_inv = Affine::from_linear(inv);
+        let t = linear_inv.transform_vector(self.translation);
+        Ok(Affine::new(inv, NVec::new(t.components.map(|c| -c))))
+    }
+}
+
+impl<T: Float + Numerical> Affine<T, 2> {
+    // anticlockwise rotation in the plane
+    pub fn rotation(angle: T) -> Self {
+        Affine::rotation_in_plane(0, 1, angle)
+    }
+}
+
+impl<T: Float + Numerical> Affine<T, 3> {
+    // angle radians about axis, right hand rule
+    pub fn from_axis_angle(axis: NVec<T, 3>, angle: T) -> Self {
+        Affine::from_quaternion(Quaternion::from_axis_angle(axis, angle))
+    }
+
+    pub fn from_quaternion(q: Quaternion<T>) -> Self {
+        Affine::from_linear(q.to_rotation_matrix())
+    }
+
+    // right handed view transform - moves eye to the origin looking down -z with up along +y
+    // Err(ZeroLength) when there's no direction to build it from - eye on top of target, or up (anti)parallel to the view
+    pub fn look_at(eye: NVec<T, 3>, target: NVec<T, 3>, up: NVec<T, 3>) -> Result<Self, NVecError> {
+        let length = |v: [T; 3]| (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+        let cross = |a: [T; 3], b: [T; 3]| [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]];
+
+        let e = eye.components;
+        let t = target.components;
+        let forward = [t[0] - e[0], t[1] - e[1], t[2] - e[2]];
+        let len = length(forward);
+        if len == T::zero() || !len.is_finite() {
+            return Err(NVecError::ZeroLength);
+        }
+        let forward = forward.map(|c| c / len);
+
+        // forward is unit length so |forward x up| = |up| sin(angle) - anything within rounding of 0 is parallel
+        let right = cross(forward, up.components);
+        let len = length(right);
+        if len <= T::epsilon() * length(up.components) || !len.is_finite() {
+            return Err(NVecError::ZeroLength);
+        }
+        let right = right.map(|c| c / len);
+        let true_up = cross(right, forward);
+        let back = forward.map(|c| -c);
+
+        let rotation = Affine::from_linear([right, true_up, back]);
+        let eye_moved = rotation.transform_vector(eye);
+        Ok(Affine::new(rotation.linear, NVec::new(eye_moved.components.map(|c| -c))))
+    }
+
+    // maps the box [left, right] x [bottom, top] x [-near, -far] on to the [-1, 1] cube (OpenGL style clip space)
+    pub fn orthographic(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Self {
+        let two = T::one() + T::one();
+
+        Affine::new(
+            [
+                [two / (right - left), T::zero(), T::zero()],
+                [T::zero(), two / (top - bottom), T::zero()],
+                [T::zero(), T::zero(), -two / (far - near)],
+            ],
+            NVec::new([
+                -(right + left) / (right - left),
+                -(top + bottom) / (top - bottom),
+                -(far + near) / (far - near),
+            ]),
+        )
+    }
+
+    // the same transform as a 4x4 homogeneous matrix, row major
+    pub fn to_homogeneous(&self) -> [[T; 4]; 4] {
+        core::array::from_fn(|i| {
+            core::array::from_fn(|j| match (i, j) {
+                (3, 3) => T::one(),
+                (3, _) => T::zero(),
+                (_, 3) => self.translation.components[i],
+                _ => self.linear[i][j],
+            })
+        })
+    }
+}
+
+// OpenGL style perspective projection - vertical field of view in radians, camera looking down -z
+// Use with project to get back to normalised device coordinates
+pub fn perspective<T: Float>(fov_y: T, aspect: T, near: T, far: T) -> [[T; 4]; 4] {
+    let two = T::one() + T::one();
+    let f = T::one() / (fov_y / two).tan();
+    let z = T::zero();
+
+    [
+        [f / aspect, z, z, z],
+        [z, f, z, z],
+        [z, z, (far + near) / (near - far), two * far * near / (near - far)],
+        [z, z, -T::one(), z],
+    ]
+}
+
+// pushes a point through a homogeneous matrix and does the perspective divide
+// None if it lands at infinity, e.g. a point in the camera's own plane under a perspective projection
+pub fn project<T: Float + Numerical>(m: [[T; 4]; 4], p: NVec<T, 3>) -> Option<NVec<T, 3>> {
+    let h = p.to_homogeneous_point();
+    let r: [T; 4] = core::array::from_fn(|i| (0..4).fold(T::zero(), |acc, j| acc + m[i][j] * h.components[j]));
+
+    NVec::new(r).from_homogeneous()
+}
+
+
+
+// Operations //
+
+// composes transforms - (a * b) applies b first then a
+impl<T: Float + Numerical, const N: usize> Mul for Affine<T, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let linear = core::array::from_fn(|i| {
+            core::array::from_fn(|j| (0..N).fold(T::zero(), |acc, k| acc + self.linear[i][k] * rhs.linear[k][j]))
+        });
+
+        Affine::new(linear, self.transform_point(rhs.translation))
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn homogeneous_test() {
+        println!("\n\nTesting for homogeneous conversions\n\n");
+        let a = NVec::new([1, 2, 3]);
+        assert_eq!(a.extend(4), NVec::new([1, 2, 3, 4]));
+        assert_eq!(a.extend(4).truncate(), a);
+        assert_eq!(NVec::new([2.0, 4.0, 6.0, 2.0]).from_homogeneous(), Some(NVec::new([1.0, 2.0, 3.0])));
+        assert_eq!(NVec::new([4, 6, 2]).from_homogeneous(), Some(NVec::new([2, 3])));
+        assert_eq!(NVec::new([4, 6, 0]).from_homogeneous(), None); //would be a divide by zero panic
+        assert_eq!(NVec::new([1.0, 0.0, 0.0, -0.0]).from_homogeneous(), None);
+        assert_eq!(NVec::new([1.0f32, 2.0]).to_homogeneous_point(), NVec::new([1.0, 2.0, 1.0]));
+        assert_eq!(NVec::new([1.0f32, 2.0]).to_homogeneous_vector(), NVec::new([1.0, 2.0, 0.0]));
+    }
+
+    #[test]
+    fn affine_test() {
+        println!("\n\nTesting for affine transforms\n\n");
+        let t = Affine::translation(NVec::new([1.0, 2.0, 3.0]));
+        let r = Affine::from_axis_angle(NVec::new([0.0, 0.0, 1.0]), FRAC_PI_2);
+        let s = Affine::scale(NVec::new([2.0, 3.0, 4.0]));
+        let p = NVec::new([1.0, 1.0, 1.0]);
+
+        assert_nvec_approx_eq!(t.transform_point(p), NVec::new([2.0, 3.0, 4.0]), epsilon = 1e-12);
+        assert_nvec_approx_eq!(t.transform_vector(p), p, epsilon = 1e-12);
+        assert_nvec_approx_eq!(r.transform_point(p), NVec::new([-1.0, 1.0, 1.0]), epsilon = 1e-12);
+        assert_nvec_approx_eq!((t * r * s).transform_point(p), t.transform_point(r.transform_point(s.transform_point(p))), epsilon = 1e-12);
+
+        let m = t * r * s * Affine::shear(0, 2, 0.5);
+        assert_nvec_approx_eq!(m.inverse().unwrap().transform_point(m.transform_point(p)), p, epsilon = 1e-12);
+        assert_eq!(Affine::scale(NVec::new([1.0, 0.0, 1.0])).inverse(), None);
+
+        // singular only by relative size, not absolute - tiny and huge scales are still invertible
+        let tiny = Affine::<f64, 3>::uniform_scale(1e-17);
+        assert_nvec_approx_eq!(tiny.inverse().unwrap().transform_point(tiny.transform_point(p)), p, epsilon = 1e-12);
+        let huge = Affine::<f64, 2>::uniform_scale(1e200);
+        assert_nvec_approx_eq!(huge.inverse().unwrap().transform_vector(NVec::new([1e200, -2e200])), NVec::new([1.0, -2.0]), epsilon = 1e-12);
+        // the third row is the first two added up, which only leaves rounding behind after elimination
+        let dependent = Affine::from_linear([[0.1, 0.2, 0.3], [0.4, 0.5, 0.6], [0.5, 0.7, 0.9]]);
+        assert_eq!(dependent.inverse(), None);
+        assert_eq!(dependent.try_inverse(), Err(NVecError::Singular));
+        assert_eq!(m.try_inverse().ok(), m.inverse());
+        assert_eq!(Affine::<f64, 2>::uniform_scale(1e-17).inverse().map(|a| a.linear[0][0]), Some(1e17));
+
+        let r2 = Affine::<f64, 2>::rotation(FRAC_PI_2);
+        assert_nvec_approx_eq!(r2.transform_point(NVec::new([1.0, 0.0])), NVec::new([0.0, 1.0]), epsilon = 1e-12);
+        assert_nvec_approx_eq!(Affine::<f64, 2>::shear(0, 1, 2.0).transform_point(NVec::new([1.0, 1.0])), NVec::new([3.0, 1.0]), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn projection_test() {
+        println!("\n\nTesting for view and projection transforms\n\n");
+        let view = Affine::look_at(NVec::new([0.0, 0.0, 5.0]), NVec::new([0.0, 0.0, 0.0]), NVec::new([0.0, 1.0, 0.0])).unwrap();
+        assert_nvec_approx_eq!(view.transform_point(NVec::new([0.0, 0.0, 0.0])), NVec::new([0.0, 0.0, -5.0]), epsilon = 1e-12);
+        assert_nvec_approx_eq!(view.transform_point(NVec::new([1.0, 0.0, 5.0])), NVec::new([1.0, 0.0, 0.0]), epsilon = 1e-12);
+
+        // looking straight along up (either way), or at itself, gives no view rather than a matrix full of NaN
+        let (eye, up) = (NVec::new([1.0, 2.0, 3.0]), NVec::new([0.0, 0.0, 2.0]));
+        assert_eq!(Affine::look_at(eye, NVec::new([1.0, 2.0, -4.0]), up), Err(NVecError::ZeroLength));
+        assert_eq!(Affine::look_at(eye, NVec::new([1.0, 2.0, 10.0]), up), Err(NVecError::ZeroLength));
+        assert_eq!(Affine::look_at(eye, eye, up), Err(NVecError::ZeroLength));
+        assert_eq!(Affine::look_at(eye, NVec::new([0.0, 0.0, 0.0]), NVec::new([0.0, 0.0, 0.0])), Err(NVecError::ZeroLength));
+        assert!(Affine::look_at(eye, NVec::new([1.0, 2.001, -4.0]), up).is_ok()); //nearly parallel is still fine
+
+        let ortho = Affine::orthographic(-2.0, 2.0, -1.0, 1.0, 1.0, 11.0);
+        assert_nvec_approx_eq!(ortho.transform_point(NVec::new([2.0, 1.0, -1.0])), NVec::new([1.0, 1.0, -1.0]), epsilon = 1e-12);
+        assert_nvec_approx_eq!(ortho.transform_point(NVec::new([-2.0, -1.0, -11.0])), NVec::new([-1.0, -1.0, 1.0]), epsilon = 1e-12);
+        assert_nvec_approx_eq!(project(ortho.to_homogeneous(), NVec::new([0.0, 0.0, -6.0])).unwrap(), NVec::new([0.0, 0.0, 0.0]), epsilon = 1e-12);
+
+        let persp = perspective(FRAC_PI_2, 1.0, 1.0, 10.0);
+        assert_nvec_approx_eq!(project(persp, NVec::new([0.0, 0.0, -1.0])).unwrap(), NVec::new([0.0, 0.0, -1.0]), epsilon = 1e-12);
+        assert_nvec_approx_eq!(project(persp, NVec::new([0.0, 0.0, -10.0])).unwrap(), NVec::new([0.0, 0.0, 1.0]), epsilon = 1e-12);
+        assert_nvec_approx_eq!(project(persp, NVec::new([2.0, -2.0, -2.0])).unwrap(), NVec::new([1.0, -1.0, 1.0 / 9.0]), epsilon = 1e-12);
+        assert_eq!(project(persp, NVec::new([1.0, 1.0, 0.0])), None); //in the camera plane
+    }
+}
@@ -0,0 +1,271 @@
+use super::*;
+
+// Approximate equality, for comparing float results without tripping over rounding
+// Three ways of saying "close enough":
+//     abs_diff_eq   |a - b| <= epsilon, good near zero
+//     relative_eq   |a - b| <= max_relative * max(|a|, |b|), good for big numbers (falls back to epsilon near zero)
+//     ulps_eq       at most max_ulps representable floats apart (falls back to epsilon near zero)
+// NVecs are approximately equal when every pair of components is
+// Integers are compared exactly, within epsilon (or max_ulps steps), so integer results can go through the same macros
+// NaN is never approximately equal to anything
+
+pub trait ApproxEq<Rhs = Self> {
+    type Epsilon: Copy;
+
+    fn default_epsilon() -> Self::Epsilon;
+    fn default_max_relative() -> Self::Epsilon;
+    fn default_max_ulps() -> u32;
+
+    fn abs_diff_eq(&self, other: &Rhs, epsilon: Self::Epsilon) -> bool;
+    fn relative_eq(&self, other: &Rhs, epsilon: Self::Epsilon, max_relative: Self::Epsilon) -> bool;
+    fn ulps_eq(&self, other: &Rhs, epsilon: Self::Epsilon, max_ulps: u32) -> bool;
+
+    // relative_eq with the default tolerances
+    fn approx_eq(&self, other: &Rhs) -> bool {
+        self.relative_eq(other, Self::default_epsilon(), Self::default_max_relative())
+    }
+}
+
+macro_rules! approx_eq_float {
+    ($(($type:ty, $bits:ty)), *) => {
+        $(
+            impl ApproxEq for $type {
+                type Epsilon = $type;
+
+                fn default_epsilon() -> $type {
+                    <$type>::EPSILON
+                }
+
+                fn default_max_relative() -> $type {
+                    <$type>::EPSILON
+                }
+
+                fn default_max_ulps() -> u32 {
+                    4
+                }
+
+                fn abs_diff_eq(&self, other: &$type, epsilon: $type) -> bool {
+                    (self - other).abs() <= epsilon
+                }
+
+                fn relative_eq(&self, other: &$type, epsilon: $type, max_relative: $type) -> bool {
+                    if self == other {
+                        return true; //also covers equal infinities
+                    }
+                    if self.is_infinite() || other.is_infinite() {
+                        return false;
+                    }
+                    let diff = (self - other).abs();
+                    diff <= epsilon || diff <= self.abs().max(other.abs()) * max_relative
+                }
+
+                fn ulps_eq(&self, other: &$type, epsilon: $type, max_ulps: u32) -> bool {
+                    if self.abs_diff_eq(other, epsilon) {
+                        return true;
+                    }
+                    if self.is_nan() || other.is_nan() || self.is_sign_negative() != other.is_sign_negative() {
+                        return false;
+                    }
+                    // same sign, so the bit patterns are in the same order as the values
+                    let (a, b) = (self.to_bits(), other.to_bits());
+                    let distance: $bits = if a > b { a - b } else { b - a };
+                    distance <= max_ulps as $bits
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! approx_eq_int {
+    ($($type:ty), *) => {
+        $(
+            impl ApproxEq for $type {
+                type Epsilon = $type;
+
+                fn default_epsilon() -> $type {
+                    0
+                }
+
+                fn default_max_relative() -> $type {
+                    0
+                }
+
+                fn default_max_ulps() -> u32 {
+                    0
+                }
+
+                // a negative epsilon allows nothing, not everything (as u128 would wrap it round to something huge)
+                #[allow(unused_comparisons)]
+                fn abs_diff_eq(&self, other: &$type, epsilon: $type) -> bool {
+                    epsilon >= 0 && self.abs_diff(*other) as u128 <= epsilon as u128
+                }
+
+                // no such thing as relative error for integers
+                fn relative_eq(&self, other: &$type, epsilon: $type, _max_relative: $type) -> bool {
+                    self.abs_diff_eq(other, epsilon)
+                }
+
+                // one unit in the last place of an integer is just 1
+                fn ulps_eq(&self, other: &$type, epsilon: $type, max_ulps: u32) -> bool {
+                    self.abs_diff_eq(other, epsilon) || self.abs_diff(*other) as u128 <= max_ulps as u128
+                }
+            }
+        )*
+    };
+}
+
+approx_eq_float!((f32, u32), (f64, u64));
+approx_eq_int!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
+impl<T: ApproxEq, const N: usize> ApproxEq for NVec<T, N> {
+    type Epsilon = T::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        T::default_epsilon()
+    }
+
+    fn default_max_relative() -> Self::Epsilon {
+        T::default_max_relative()
+    }
+
+    fn default_max_ulps() -> u32 {
+        T::default_max_ulps()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.components.iter().zip(other.components.iter()).all(|(a, b)| a.abs_diff_eq(b, epsilon))
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: Self::Epsilon, max_relative: Self::Epsilon) -> bool {
+        self.components.iter().zip(other.components.iter()).all(|(a, b)| a.relative_eq(b, epsilon, max_relative))
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+        self.components.iter().zip(other.components.iter()).all(|(a, b)| a.ulps_eq(b, epsilon, max_ulps))
+    }
+}
+
+
+
+// Assertion macros //
+// Work on anything ApproxEq, so scalars too, e.g.
+//     assert_nvec_approx_eq!(a, b);                      relative with the default tolerances
+//     assert_nvec_approx_eq!(a, b, epsilon = 1e-9);      absolute
+//     assert_nvec_approx_eq!(a, b, relative = 1e-6);     relative
+//     assert_nvec_approx_eq!(a, b, ulps = 8);            ULPs
+
+#[macro_export]
+macro_rules! assert_nvec_approx_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_nvec_approx_eq!(@check $left, $right, "relative (default tolerances)", |l, r| {
+            $crate::ApproxEq::approx_eq(l, r)
+        })
+    };
+    ($left:expr, $right:expr, epsilon = $eps:expr $(,)?) => {
+        $crate::assert_nvec_approx_eq!(@check $left, $right, concat!("epsilon = ", stringify!($eps)), |l, r| {
+            $crate::ApproxEq::abs_diff_eq(l, r, $eps)
+        })
+    };
+    ($left:expr, $right:expr, relative = $rel:expr $(,)?) => {
+        $crate::assert_nvec_approx_eq!(@check $left, $right, concat!("relative = ", stringify!($rel)), |l, r| {
+            $crate::approx_eq::relative_eq_default_epsilon(l, r, $rel)
+        })
+    };
+    ($left:expr, $right:expr, ulps = $ulps:expr $(,)?) => {
+        $crate::assert_nvec_approx_eq!(@check $left, $right, concat!("ulps = ", stringify!($ulps)), |l, r| {
+            $crate::approx_eq::ulps_eq_default_epsilon(l, r, $ulps)
+        })
+    };
+    (@check $left:expr, $right:expr, $how:expr, |$l:ident, $r:ident| $cmp:expr) => {
+        match (&$left, &$right) {
+            ($l, $r) => {
+                if !$cmp {
+                    panic!("assertion `left ≈ right` failed ({})\n  left: {:?}\n right: {:?}", $how, $l, $r);
+                }
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! assert_nvec_approx_ne {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if $crate::ApproxEq::approx_eq(left, right) {
+                    panic!("assertion `left ≉ right` failed\n  left: {:?}\n right: {:?}", left, right);
+                }
+            }
+        }
+    };
+}
+
+// relative_eq/ulps_eq with the default epsilon, which the macros can't name the type of
+#[doc(hidden)]
+pub fn relative_eq_default_epsilon<A: ApproxEq<B>, B>(left: &A, right: &B, max_relative: A::Epsilon) -> bool {
+    left.relative_eq(right, A::default_epsilon(), max_relative)
+}
+
+#[doc(hidden)]
+pub fn ulps_eq_default_epsilon<A: ApproxEq<B>, B>(left: &A, right: &B, max_ulps: u32) -> bool {
+    left.ulps_eq(right, A::default_epsilon(), max_ulps)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scalar_test() {
+        println!("\n\nTesting for approximate equality of scalars\n\n");
+        assert!((0.1 + 0.2).approx_eq(&0.3));
+        assert_ne!(0.1 + 0.2, 0.3);
+        assert!(1.0f32.abs_diff_eq(&1.05, 0.1));
+        assert!(!1.0f32.abs_diff_eq(&1.2, 0.1));
+        assert!(1e10f64.relative_eq(&(1e10 + 1.0), 0.0, 1e-9));
+        assert!(!1e10f64.abs_diff_eq(&(1e10 + 1.0), 0.5));
+
+        let next = f64::from_bits(1.0f64.to_bits() + 3);
+        assert!(1.0.ulps_eq(&next, 0.0, 3));
+        assert!(!1.0.ulps_eq(&next, 0.0, 2));
+        assert!(!(-0.5f64).ulps_eq(&0.5, 0.0, u32::MAX)); //different signs
+
+        assert!(!f64::NAN.approx_eq(&f64::NAN));
+        assert!(f64::INFINITY.approx_eq(&f64::INFINITY));
+        assert!(!f64::INFINITY.approx_eq(&f64::MAX));
+
+        assert!(5i32.approx_eq(&5));
+        assert!(!5u8.approx_eq(&6));
+        assert!(5u8.abs_diff_eq(&7, 2));
+        assert!(i64::MIN.ulps_eq(&(i64::MIN + 1), 0, 1));
+        assert!(!0i32.abs_diff_eq(&1_000_000, -1)); //negative epsilon doesn't wrap round to a huge tolerance
+        assert!(!7i8.abs_diff_eq(&7, i8::MIN));
+        assert!(!NVec::new([1i64, 2]).abs_diff_eq(&NVec::new([1, 900]), -5));
+    }
+
+    #[test]
+    fn nvec_test() {
+        println!("\n\nTesting for approximate equality of NVecs\n\n");
+        let a = NVec::new([0.1, 0.2, 0.3]) * 3.0;
+        let b = NVec::new([0.3, 0.6, 0.9]);
+        assert_ne!(a, b);
+        assert!(a.approx_eq(&b));
+        assert!(!a.abs_diff_eq(&NVec::new([0.3, 0.6, 0.91]), 1e-3));
+
+        assert_nvec_approx_eq!(a, b);
+        assert_nvec_approx_eq!(a, NVec::new([0.3, 0.6, 0.9005]), epsilon = 1e-3);
+        assert_nvec_approx_eq!(NVec::new([1e6f32, 1.0]), NVec::new([1.00001e6, 1.0]), relative = 1e-4);
+        assert_nvec_approx_eq!(a, b, ulps = 4);
+        assert_nvec_approx_eq!(NVec::new([1, 2]), NVec::new([1, 2]));
+        assert_nvec_approx_eq!(0.1 + 0.2, 0.3);
+        assert_nvec_approx_ne!(a, NVec::new([0.3, 0.6, 1.0]));
+    }
+
+    #[test]
+    #[should_panic(expected = "epsilon = 1e-9")]
+    fn failure_message_test() {
+        assert_nvec_approx_eq!(NVec::new([1.0, 2.0]), NVec::new([1.0, 2.001]), epsilon = 1e-9);
+    }
+}
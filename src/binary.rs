@@ -0,0 +1,524 @@
+use super::*;
+use checksum::{crc32, crc32_update};
+use std::fmt;
+use std::io::{self, Read, Write};
+
+// A compact binary format for storing lots of vectors
+// A single NVec is just its components back to back, N * size bytes, in whichever byte order you choose
+// A collection of them gets a 20 byte header in front and a checksum behind:
+//     0   "NVEC"
+//     4   version (1)
+//     5   flags - bit 0 set for big endian, the rest 0
+//     6   type tag, see binary_element! below
+//     7   bytes per component
+//     8   N as a u32
+//     12  number of vectors as a u64
+//     20  the vectors, one after the other
+//     end CRC-32 of everything before it, as a u32
+// N, the count and the checksum are in the same byte order as the components
+// NVecWriter/NVecReader stream the same format, so billions of vectors never have to be in memory at once
+
+const MAGIC: &[u8; 4] = b"NVEC";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = 20;
+
+#[derive(Debug)]
+pub enum BinaryError {
+    Io(io::Error),
+    // not this format, or cut short
+    Format(String),
+    Version(u8),
+    // the data holds a different component type than asked for
+    Type { expected: &'static str, found: String },
+    // reading, the data's N isn't the one asked for - writing, N is bigger than the header can hold (expected is then u32::MAX)
+    Dimension { expected: usize, found: usize },
+    Checksum { expected: u32, found: u32 },
+    // a component that can't exist, e.g. a NaN in a Finite or an Interval with lo > hi
+    InvalidValue { vector: u64, component: usize },
+    // a writer given more or fewer vectors than it promised in the header
+    Count { expected: u64, found: u64 },
+}
+
+impl fmt::Display for BinaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BinaryError::Io(e) => write!(f, "{}", e),
+            BinaryError::Format(message) => write!(f, "invalid NVec data: {}", message),
+            BinaryError::Version(v) => write!(f, "unsupported format version {}", v),
+            BinaryError::Type { expected, found } => write!(f, "expected {} components but the data holds {}", expected, found),
+            BinaryError::Dimension { expected, found } => write!(f, "expected vectors of {} components but the data holds {}", expected, found),
+            BinaryError::Checksum { expected, found } => write!(f, "checksum mismatch: stored {:08x} but the data gives {:08x}", expected, found),
+            BinaryError::InvalidValue { vector, component } => write!(f, "invalid value in vector {} component {}", vector, component),
+            BinaryError::Count { expected, found } => write!(f, "expected {} vectors but got {}", expected, found),
+        }
+    }
+}
+
+impl std::error::Error for BinaryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BinaryError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for BinaryError {
+    fn from(e: io::Error) -> Self {
+        if e.kind() == io::ErrorKind::UnexpectedEof {
+            BinaryError::Format("data ends early".to_string())
+        } else {
+            BinaryError::Io(e)
+        }
+    }
+}
+
+
+
+// Component types //
+
+// Anything that can be a component in the binary format - every Numerical type in the crate
+pub trait BinaryElement: Numerical {
+    const TAG: u8;
+    const NAME: &'static str;
+    const SIZE: usize;
+
+    fn write_bytes(self, endian: Endian, out: &mut Vec<u8>);
+    // None if the bytes aren't a valid value of the type
+    fn read_bytes(bytes: &[u8], endian: Endian) -> Option<Self>;
+}
+
+macro_rules! binary_element {
+    ($(($type:ty, $tag:literal)), *) => {
+        $(
+            impl BinaryElement for $type {
+                const TAG: u8 = $tag;
+                const NAME: &'static str = stringify!($type);
+                const SIZE: usize = std::mem::size_of::<$type>();
+
+                fn write_bytes(self, endian: Endian, out: &mut Vec<u8>) {
+                    match endian {
+                        Endian::Little => out.extend_from_slice(&self.to_le_bytes()),
+                        Endian::Big => out.extend_from_slice(&self.to_be_bytes()),
+                    }
+                }
+
+                fn read_bytes(bytes: &[u8], endian: Endian) -> Option<Self> {
+                    let bytes = bytes.try_into().ok()?;
+                    Some(match endian {
+                        Endian::Little => <$type>::from_le_bytes(bytes),
+                        Endian::Big => <$type>::from_be_bytes(bytes),
+                    })
+                }
+            }
+        )*
+    };
+}
+
+// The tags are part of the format, so never renumber them - only add new ones on the end
+binary_element!(
+    (i8, 1), (i16, 2), (i32, 3), (i64, 4), (i128, 5),
+    (u8, 6), (u16, 7), (u32, 8), (u64, 9), (u128, 10),
+    (f32, 11), (f64, 12)
+);
+
+// the half floats go through their raw bits
+macro_rules! half_element {
+    ($(($type:ident, $tag:literal)), *) => {
+        $(
+            impl BinaryElement for $type {
+                const TAG: u8 = $tag;
+                const NAME: &'static str = stringify!($type);
+                const SIZE: usize = 2;
+
+                fn write_bytes(self, endian: Endian, out: &mut Vec<u8>) {
+                    self.to_bits().write_bytes(endian, out)
+                }
+
+                fn read_bytes(bytes: &[u8], endian: Endian) -> Option<Self> {
+                    u16::read_bytes(bytes, endian).map($type::from_bits)
+                }
+            }
+        )*
+    };
+}
+
+half_element!((F16, 13), (BF16, 14));
+
+// Finite and Interval have rules about what they can hold, so those get checked on the way back in
+macro_rules! float_wrapper_element {
+    ($(($type:ty, $finite_tag:literal, $interval_tag:literal)), *) => {
+        $(
+            impl BinaryElement for Finite<$type> {
+                const TAG: u8 = $finite_tag;
+                const NAME: &'static str = concat!("Finite<", stringify!($type), ">");
+                const SIZE: usize = <$type as BinaryElement>::SIZE;
+
+                fn write_bytes(self, endian: Endian, out: &mut Vec<u8>) {
+                    self.get().write_bytes(endian, out)
+                }
+
+                fn read_bytes(bytes: &[u8], endian: Endian) -> Option<Self> {
+                    Finite::<$type>::new(<$type>::read_bytes(bytes, endian)?).ok()
+                }
+            }
+
+            impl BinaryElement for Interval<$type> {
+                const TAG: u8 = $interval_tag;
+                const NAME: &'static str = concat!("Interval<", stringify!($type), ">");
+                const SIZE: usize = 2 * <$type as BinaryElement>::SIZE;
+
+                fn write_bytes(self, endian: Endian, out: &mut Vec<u8>) {
+                    self.lo.write_bytes(endian, out);
+                    self.hi.write_bytes(endian, out);
+                }
+
+                fn read_bytes(bytes: &[u8], endian: Endian) -> Option<Self> {
+                    let (lo, hi) = bytes.split_at(<$type as BinaryElement>::SIZE);
+                    let (lo, hi) = (<$type>::read_bytes(lo, endian)?, <$type>::read_bytes(hi, endian)?);
+                    if lo.is_nan() && hi.is_nan() {
+                        return Some(Interval::<$type>::empty()); //what sqrt of a negative interval gives
+                    }
+                    (lo <= hi).then_some(Interval { lo, hi }) //also turns away a lone NaN bound
+                }
+            }
+        )*
+    };
+}
+
+float_wrapper_element!((f32, 15, 17), (f64, 16, 18));
+
+fn tag_name(tag: u8) -> String {
+    const NAMES: [&str; 18] = [
+        "i8", "i16", "i32", "i64", "i128", "u8", "u16", "u32", "u64", "u128", "f32", "f64",
+        "F16", "BF16", "Finite<f32>", "Finite<f64>", "Interval<f32>", "Interval<f64>",
+    ];
+    match NAMES.get((tag as usize).wrapping_sub(1)) {
+        Some(name) => name.to_string(),
+        None => format!("unknown type tag {}", tag),
+    }
+}
+
+
+
+// Single vectors, no header //
+
+impl<T: BinaryElement, const N: usize> NVec<T, N> {
+    pub fn to_bytes(&self, endian: Endian) -> Vec<u8> {
+        let mut out = Vec::with_capacity(N * T::SIZE);
+        for c in self.components {
+            c.write_bytes(endian, &mut out);
+        }
+        out
+    }
+
+    // exactly N * size bytes, nothing else
+    pub fn from_bytes(bytes: &[u8], endian: Endian) -> Result<Self, BinaryError> {
+        if bytes.len() != N * T::SIZE {
+            return Err(BinaryError::Format(format!("expected {} bytes but found {}", N * T::SIZE, bytes.len())));
+        }
+        decode(bytes, endian, 0)
+    }
+}
+
+fn decode<T: BinaryElement, const N: usize>(bytes: &[u8], endian: Endian, vector: u64) -> Result<NVec<T, N>, BinaryError> {
+    let mut components = Vec::with_capacity(N);
+    for (component, chunk) in bytes.chunks_exact(T::SIZE).enumerate() {
+        components.push(T::read_bytes(chunk, endian).ok_or(BinaryError::InvalidValue { vector, component })?);
+    }
+    Ok(NVec::try_from(components).unwrap_or_else(|_| unreachable!())) //N * SIZE bytes in, N components out
+}
+
+
+
+// Collections, with the header //
+
+pub fn to_bytes<T: BinaryElement, const N: usize>(vecs: &[NVec<T, N>], endian: Endian) -> Vec<u8> {
+    let mut out = Vec::with_capacity(HEADER_LEN + vecs.len() * N * T::SIZE + 4);
+    let mut writer = NVecWriter::new(&mut out, endian, vecs.len() as u64).unwrap_or_else(|_| unreachable!()); //writing to a Vec can't fail
+    for v in vecs {
+        writer.write(v).unwrap_or_else(|_| unreachable!());
+    }
+    writer.finish().unwrap_or_else(|_| unreachable!());
+    out
+}
+
+// The byte order comes from the header, the type and N have to match what's asked for
+pub fn from_bytes<T: BinaryElement, const N: usize>(bytes: &[u8]) -> Result<Vec<NVec<T, N>>, BinaryError> {
+    let mut rest = bytes;
+    let vecs = NVecReader::new(&mut rest)?.collect::<Result<Vec<_>, _>>()?;
+    if !rest.is_empty() {
+        return Err(BinaryError::Format(format!("{} unexpected bytes after the checksum", rest.len())));
+    }
+    Ok(vecs)
+}
+
+pub struct NVecWriter<W: Write, T, const N: usize> {
+    writer: W,
+    endian: Endian,
+    count: u64,
+    written: u64,
+    crc: u32,
+    buf: Vec<u8>,
+    marker: std::marker::PhantomData<T>,
+}
+
+impl<W: Write, T: BinaryElement, const N: usize> NVecWriter<W, T, N> {
+    // the header goes out straight away, so the number of vectors has to be known up front
+    pub fn new(mut writer: W, endian: Endian, count: u64) -> Result<Self, BinaryError> {
+        let n = u32::try_from(N).map_err(|_| BinaryError::Dimension { expected: u32::MAX as usize, found: N })?;
+        let mut header = Vec::with_capacity(HEADER_LEN);
+        header.extend_from_slice(MAGIC);
+        header.push(VERSION);
+        header.push(if endian == Endian::Big { 1 } else { 0 });
+        header.push(T::TAG);
+        header.push(T::SIZE as u8);
+        n.write_bytes(endian, &mut header);
+        count.write_bytes(endian, &mut header);
+        writer.write_all(&header)?;
+
+        let crc = crc32(&header);
+        Ok(NVecWriter { writer, endian, count, written: 0, crc, buf: Vec::with_capacity(N * T::SIZE), marker: std::marker::PhantomData })
+    }
+
+    pub fn write(&mut self, v: &NVec<T, N>) -> Result<(), BinaryError> {
+        if self.written == self.count {
+            return Err(BinaryError::Count { expected: self.count, found: self.count + 1 });
+        }
+        self.buf.clear();
+        for c in v.components {
+            c.write_bytes(self.endian, &mut self.buf);
+        }
+        self.writer.write_all(&self.buf)?;
+        self.crc = crc32_update(self.crc, &self.buf);
+        self.written += 1;
+        Ok(())
+    }
+
+    // writes the checksum, an error if fewer vectors were written than promised
+    pub fn finish(mut self) -> Result<W, BinaryError> {
+        if self.written != self.count {
+            return Err(BinaryError::Count { expected: self.count, found: self.written });
+        }
+        self.buf.clear();
+        self.crc.write_bytes(self.endian, &mut self.buf);
+        self.writer.write_all(&self.buf)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+// Vectors come out one at a time as they're read
+// The checksum can only be checked once everything has been read, so a mismatch is an Err after the last vector,
+// collect in to a Result (or check the last item) before trusting the data
+pub struct NVecReader<R: Read, T, const N: usize> {
+    reader: R,
+    endian: Endian,
+    count: u64,
+    read: u64,
+    crc: u32,
+    done: bool,
+    buf: Vec<u8>, //one vector's worth of bytes, reused for every read
+    marker: std::marker::PhantomData<T>,
+}
+
+impl<R: Read, T: BinaryElement, const N: usize> NVecReader<R, T, N> {
+    pub fn new(mut reader: R) -> Result<Self, BinaryError> {
+        let mut header = [0u8; HEADER_LEN];
+        reader.read_exact(&mut header)?;
+
+        if &header[..4] != MAGIC {
+            return Err(BinaryError::Format("missing the NVEC magic string".to_string()));
+        }
+        if header[4] != VERSION {
+            return Err(BinaryError::Version(header[4]));
+        }
+        let endian = match header[5] {
+            0 => Endian::Little,
+            1 => Endian::Big,
+            flags => return Err(BinaryError::Format(format!("unknown flags {:#04x}", flags))),
+        };
+        if header[6] != T::TAG || header[7] as usize != T::SIZE {
+            return Err(BinaryError::Type { expected: T::NAME, found: tag_name(header[6]) });
+        }
+        let n = u32::read_bytes(&header[8..12], endian).unwrap_or_else(|| unreachable!()) as usize;
+        if n != N {
+            return Err(BinaryError::Dimension { expected: N, found: n });
+        }
+        let count = u64::read_bytes(&header[12..20], endian).unwrap_or_else(|| unreachable!());
+
+        Ok(NVecReader { reader, endian, count, read: 0, crc: crc32(&header), done: false, buf: vec![0; N * T::SIZE], marker: std::marker::PhantomData })
+    }
+
+    // how many vectors the header says there are (not count(), that's Iterator's and reads them all)
+    pub fn len(&self) -> u64 {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn endian(&self) -> Endian {
+        self.endian
+    }
+
+    fn next_vec(&mut self) -> Result<NVec<T, N>, BinaryError> {
+        self.reader.read_exact(&mut self.buf)?;
+        self.crc = crc32_update(self.crc, &self.buf);
+        let v = decode(&self.buf, self.endian, self.read)?;
+        self.read += 1;
+        Ok(v)
+    }
+
+    fn check_crc(&mut self) -> Result<(), BinaryError> {
+        let mut stored = [0u8; 4];
+        self.reader.read_exact(&mut stored)?;
+        let stored = u32::read_bytes(&stored, self.endian).unwrap_or_else(|| unreachable!());
+        if stored != self.crc {
+            return Err(BinaryError::Checksum { expected: stored, found: self.crc });
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read, T: BinaryElement, const N: usize> Iterator for NVecReader<R, T, N> {
+    type Item = Result<NVec<T, N>, BinaryError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if self.read == self.count {
+            self.done = true;
+            return self.check_crc().err().map(Err);
+        }
+        let v = self.next_vec();
+        if v.is_err() {
+            self.done = true; //no way to find the start of the next vector after a bad one
+        }
+        Some(v)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // builds 4 vectors from small integers, so they're valid for every type
+    macro_rules! round_trip {
+        ($($type:ty => $make:expr), * $(,)?) => {
+            $(
+                let make: fn(u8) -> $type = $make;
+                let vecs: Vec<NVec<$type, 3>> = (0..4).map(|i| NVec::new([make(i), make(i + 1), make(i * 3)])).collect();
+                for endian in [Endian::Little, Endian::Big] {
+                    // one at a time
+                    let bytes = vecs[1].to_bytes(endian);
+                    assert_eq!(bytes.len(), 3 * <$type as BinaryElement>::SIZE);
+                    assert_eq!(NVec::<$type, 3>::from_bytes(&bytes, endian).unwrap(), vecs[1]);
+
+                    // all together
+                    let bytes = to_bytes(&vecs, endian);
+                    assert_eq!(bytes.len(), HEADER_LEN + 12 * <$type as BinaryElement>::SIZE + 4);
+                    assert_eq!(from_bytes::<$type, 3>(&bytes).unwrap(), vecs);
+
+                    // streamed
+                    let mut writer = NVecWriter::new(Vec::new(), endian, vecs.len() as u64).unwrap();
+                    for v in &vecs {
+                        writer.write(v).unwrap();
+                    }
+                    let streamed = writer.finish().unwrap();
+                    assert_eq!(streamed, bytes);
+                    let reader = NVecReader::<_, $type, 3>::new(&streamed[..]).unwrap();
+                    assert_eq!((reader.len(), reader.endian()), (4, endian));
+                    assert_eq!(reader.collect::<Result<Vec<_>, _>>().unwrap(), vecs);
+                }
+            )*
+        };
+    }
+
+    #[test]
+    fn round_trip_test() {
+        println!("\n\nTesting for binary round trips of every numerical type\n\n");
+        round_trip!(
+            i8 => |i| i as i8, i16 => |i| -(i as i16), i32 => |i| i as i32 * 1000, i64 => |i| i as i64 - 2, i128 => |i| i128::MAX - i as i128,
+            u8 => |i| i, u16 => |i| i as u16, u32 => |i| u32::MAX - i as u32, u64 => |i| i as u64, u128 => |i| i as u128,
+            f32 => |i| i as f32 / 3.0, f64 => |i| -(i as f64) / 7.0,
+            F16 => |i| F16::from_f32(i as f32 * 0.5), BF16 => |i| BF16::from_f32(i as f32),
+            Finite<f32> => |i| Finite::<f32>::new(i as f32).unwrap(), Finite<f64> => |i| Finite::<f64>::new(i as f64 * 1e300).unwrap(),
+            Interval<f32> => |i| Interval::new(i as f32, i as f32 + 0.5), Interval<f64> => |i| Interval::new(-(i as f64), i as f64),
+        );
+
+        // the exact layout, big endian
+        let bytes = to_bytes(&[NVec::new([1u16, 2])], Endian::Big);
+        assert_eq!(&bytes[..HEADER_LEN], b"NVEC\x01\x01\x07\x02\x00\x00\x00\x02\x00\x00\x00\x00\x00\x00\x00\x01");
+        assert_eq!(&bytes[HEADER_LEN..HEADER_LEN + 4], &[0, 1, 0, 2]);
+        assert_eq!(bytes[HEADER_LEN + 4..], crc32(&bytes[..HEADER_LEN + 4]).to_be_bytes());
+
+        let empty: Vec<NVec<f64, 4>> = Vec::new();
+        assert_eq!(from_bytes::<f64, 4>(&to_bytes(&empty, Endian::Little)).unwrap(), empty);
+
+        // empty intervals (NaN..NaN) come back empty - they never compare equal, so check them by hand
+        let nothing = Interval::new(-4.0f64, -1.0).sqrt();
+        let vecs = vec![NVec::new([nothing, Interval::new(1.0, 2.0)]), NVec::new([Interval::point(3.0), Interval::<f64>::empty()])];
+        for endian in [Endian::Little, Endian::Big] {
+            let read = from_bytes::<Interval<f64>, 2>(&to_bytes(&vecs, endian)).unwrap();
+            assert!(read[0].x().is_empty() && read[1].y().is_empty());
+            assert_eq!((read[0].y(), read[1].x()), (Interval::new(1.0, 2.0), Interval::point(3.0)));
+        }
+        let mut half_nan = Vec::new();
+        Interval { lo: f32::NAN, hi: 1.0f32 }.write_bytes(Endian::Little, &mut half_nan);
+        assert_eq!(Interval::<f32>::read_bytes(&half_nan, Endian::Little), None);
+    }
+
+    #[test]
+    fn error_test() {
+        println!("\n\nTesting for binary format errors\n\n");
+        let vecs = vec![NVec::new([1.0f64, 2.0]), NVec::new([3.0, 4.0])];
+        let bytes = to_bytes(&vecs, Endian::Little);
+
+        assert!(matches!(from_bytes::<f32, 2>(&bytes), Err(BinaryError::Type { expected: "f32", found }) if found == "f64"));
+        assert!(matches!(from_bytes::<f64, 3>(&bytes), Err(BinaryError::Dimension { expected: 3, found: 2 })));
+        assert!(matches!(from_bytes::<f64, 2>(&bytes[..bytes.len() - 6]), Err(BinaryError::Format(_))));
+        assert!(matches!(from_bytes::<f64, 2>(&[bytes.clone(), vec![0]].concat()), Err(BinaryError::Format(_))));
+        assert!(matches!(from_bytes::<f64, 2>(b"NOPE"), Err(BinaryError::Format(_))));
+
+        let mut corrupt = bytes.clone();
+        corrupt[HEADER_LEN + 3] ^= 0x10;
+        assert!(matches!(from_bytes::<f64, 2>(&corrupt), Err(BinaryError::Checksum { .. })));
+        // streaming hands out the vectors before it can know, then reports it
+        let items: Vec<_> = NVecReader::<_, f64, 2>::new(&corrupt[..]).unwrap().collect();
+        assert_eq!(items.len(), 3);
+        assert!(items[..2].iter().all(|v| v.is_ok()) && matches!(items[2], Err(BinaryError::Checksum { .. })));
+
+        let mut version = bytes.clone();
+        version[4] = 9;
+        assert!(matches!(from_bytes::<f64, 2>(&version), Err(BinaryError::Version(9))));
+
+        // a NaN can't be read back in as a Finite, even with a good checksum
+        let nan = to_bytes(&[NVec::new([1.0f64, f64::NAN])], Endian::Little);
+        let mut as_finite = nan.clone();
+        as_finite[6] = <Finite<f64> as BinaryElement>::TAG;
+        let end = as_finite.len() - 4;
+        let crc = crc32(&as_finite[..end]).to_le_bytes();
+        as_finite[end..].copy_from_slice(&crc);
+        assert!(matches!(from_bytes::<Finite<f64>, 2>(&as_finite), Err(BinaryError::InvalidValue { vector: 0, component: 1 })));
+
+        let mut writer = NVecWriter::new(Vec::new(), Endian::Little, 1).unwrap();
+        writer.write(&NVec::new([1u8])).unwrap();
+        assert!(matches!(writer.write(&NVec::new([2u8])), Err(BinaryError::Count { expected: 1, found: 2 })));
+        let short = NVecWriter::<_, u8, 1>::new(Vec::new(), Endian::Little, 2).unwrap();
+        assert!(matches!(short.finish(), Err(BinaryError::Count { expected: 2, found: 0 })));
+
+        // N has to fit the header's u32 - the writer never builds an NVec, so the type alone is enough to check
+        #[cfg(target_pointer_width = "64")]
+        {
+            const TOO_BIG: usize = u32::MAX as usize + 1;
+            let mut out = Vec::new();
+            assert!(matches!(NVecWriter::<_, u8, TOO_BIG>::new(&mut out, Endian::Little, 0), Err(BinaryError::Dimension { expected: 4294967295, found: TOO_BIG })));
+            assert!(out.is_empty()); //nothing written with the wrong N in it
+        }
+    }
+}
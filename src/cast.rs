@@ -0,0 +1,164 @@
+use super::*;
+use num_traits::{Bounded, Float, NumCast, ToPrimitive, Zero};
+use std::fmt;
+
+// Explicitly changing the component type
+//     cast            `as` semantics - ints wrap, floats to ints truncate and saturate, never fails
+//     try_cast        fails on the first component that doesn't fit, and says which one
+//     saturating_cast anything too big or small is pinned to the target's max/min (NaN goes to 0)
+//     round_cast      floats to ints, rounding to nearest (halfway away from zero) rather than truncating
+// Promote still handles the implicit conversions during arithmetic, these are for when you want to choose
+
+// The first component try_cast couldn't fit in to the target type
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CastError<T> {
+    pub index: usize,
+    pub value: T,
+    pub target: &'static str,
+}
+
+impl<T: fmt::Display> fmt::Display for CastError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "component {} ({}) is out of range for {}", self.index, self.value, self.target)
+    }
+}
+
+impl<T: fmt::Debug + fmt::Display> std::error::Error for CastError<T> {}
+
+impl<T: Numerical, const N: usize> NVec<T, N> {
+    pub fn cast<U>(self) -> NVec<U, N>
+    where
+        T: AsPrimitive<U>,
+        U: Copy + 'static,
+    {
+        NVec { components: self.components.map(|c| c.as_()) }
+    }
+
+    // floats to ints drop the fractional part, as with `as`, so 2.7 -> 2 is fine but 300.0 -> u8 isn't
+    pub fn try_cast<U>(self) -> Result<NVec<U, N>, CastError<T>>
+    where
+        T: ToPrimitive,
+        U: NumCast + Copy,
+    {
+        let mut components = Vec::with_capacity(N);
+        for (index, c) in self.components.iter().enumerate() {
+            match checked_cast::<T, U>(*c) {
+                Some(u) => components.push(u),
+                None => return Err(CastError { index, value: *c, target: std::any::type_name::<U>() }),
+            }
+        }
+        Ok(NVec::try_from(components).unwrap_or_else(|_| unreachable!())) //one pushed per component
+    }
+
+    pub fn saturating_cast<U>(self) -> NVec<U, N>
+    where
+        T: ToPrimitive + Zero,
+        U: NumCast + Bounded + Zero + Copy,
+    {
+        NVec {
+            components: self.components.map(|c| match checked_cast::<T, U>(c) {
+                Some(u) => u,
+                None if c < T::zero() => U::min_value(),
+                None if c > T::zero() => U::max_value(),
+                None => U::zero(), //NaN
+            }),
+        }
+    }
+}
+
+impl<T: Numerical + Float, const N: usize> NVec<T, N> {
+    // `as` after rounding, so out of range values saturate and NaN becomes 0
+    pub fn round_cast<U>(self) -> NVec<U, N>
+    where
+        T: AsPrimitive<U>,
+        U: Copy + 'static,
+    {
+        NVec { components: self.components.map(|c| c.round().as_()) }
+    }
+}
+
+// NumCast lets a finite float overflow to infinity when narrowing (e.g. 1e300f64 -> f32), which we count as out of range too
+fn checked_cast<T: ToPrimitive + Copy, U: NumCast>(c: T) -> Option<U> {
+    let u = U::from(c)?;
+    let was_finite = c.to_f64().is_some_and(|x| x.is_finite());
+    let now_infinite = u.to_f64().is_some_and(|x| x.is_infinite());
+    if was_finite && now_infinite {
+        None
+    } else {
+        Some(u)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // every pair of the 12 numerical! types, with values that fit in all of them
+    macro_rules! cast_tests {
+        ($($from:ty), *) => {
+            $(
+                cast_tests!(@to $from; i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, f32, f64);
+            )*
+        };
+        (@to $from:ty; $($to:ty), *) => {
+            $(
+                let v: NVec<$from, 3> = NVec::new([0 as $from, 7 as $from, 100 as $from]);
+                let expected: NVec<$to, 3> = NVec::new([0 as $to, 7 as $to, 100 as $to]);
+                assert_eq!(v.cast::<$to>(), expected);
+                assert_eq!(v.try_cast::<$to>(), Ok(expected));
+                assert_eq!(v.saturating_cast::<$to>(), expected);
+            )*
+        };
+    }
+
+    macro_rules! round_cast_tests {
+        ($($from:ty), *) => {
+            $(
+                let v: NVec<$from, 3> = NVec::new([2.5, 7.49, 99.6]);
+                assert_eq!(v.round_cast::<i8>(), NVec::new([3, 7, 100]));
+                assert_eq!(v.round_cast::<u128>(), NVec::new([3, 7, 100]));
+                assert_eq!(v.round_cast::<f32>(), NVec::new([3.0, 7.0, 100.0]));
+            )*
+        };
+    }
+
+    #[test]
+    fn same_range_test() {
+        println!("\n\nTesting for casts between every numerical type\n\n");
+        cast_tests!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, f32, f64);
+        round_cast_tests!(f32, f64);
+    }
+
+    #[test]
+    fn out_of_range_test() {
+        println!("\n\nTesting for out of range casts\n\n");
+        let v = NVec::new([1.9, -300.0, f64::NAN]);
+        assert_eq!(v.cast::<i8>(), NVec::new([1, -128, 0]));
+        assert_eq!(v.round_cast::<i8>(), NVec::new([2, -128, 0]));
+        assert_eq!(v.saturating_cast::<i8>(), NVec::new([1, -128, 0]));
+
+        let err = v.try_cast::<i8>().unwrap_err();
+        assert_eq!(err.index, 1);
+        assert_eq!(err.value, -300.0);
+        assert_eq!(err.to_string(), "component 1 (-300) is out of range for i8");
+
+        let n = NVec::new([255i32, 256, -1]);
+        assert_eq!(n.cast::<u8>(), NVec::new([255, 0, 255])); //wraps
+        assert_eq!(n.saturating_cast::<u8>(), NVec::new([255, 255, 0]));
+        assert_eq!(n.try_cast::<u8>(), Err(CastError { index: 1, value: 256, target: "u8" }));
+        assert_eq!(n.try_cast::<i16>(), Ok(NVec::new([255, 256, -1])));
+
+        let big = NVec::new([u128::MAX, 1]);
+        assert_eq!(big.try_cast::<i128>().unwrap_err().index, 0);
+        assert_eq!(big.saturating_cast::<i64>(), NVec::new([i64::MAX, 1]));
+
+        // narrowing floats keeps precision loss but not overflow
+        let f = NVec::new([0.1, 1e300, -1e300]);
+        assert_eq!(f.cast::<f32>(), NVec::new([0.1, f32::INFINITY, f32::NEG_INFINITY]));
+        assert_eq!(f.try_cast::<f32>().unwrap_err().index, 1);
+        assert_eq!(f.saturating_cast::<f32>(), NVec::new([0.1, f32::MAX, f32::MIN]));
+        assert_eq!(NVec::new([f64::INFINITY]).try_cast::<f32>(), Ok(NVec::new([f32::INFINITY])));
+    }
+}
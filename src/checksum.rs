@@ -0,0 +1,89 @@
+// CRC-32 as used by zip (and gzip, png...)
+// Shared by the .npz reader, which checks each array against the archive, and the binary format's trailing checksum
+
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    crc32_update(0, bytes)
+}
+
+// carries on a crc32 from earlier bytes, so crc32_update(crc32(a), b) == crc32(a ++ b)
+// slice-by-8: eight table lookups take care of eight bytes at once, then whatever's left goes a byte at a time
+pub(crate) fn crc32_update(crc: u32, bytes: &[u8]) -> u32 {
+    let mut crc = !crc;
+    let mut chunks = bytes.chunks_exact(8);
+    for chunk in &mut chunks {
+        let one = crc ^ u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        let two = u32::from_le_bytes([chunk[4], chunk[5], chunk[6], chunk[7]]);
+        crc = CRC_TABLES[7][(one & 0xff) as usize]
+            ^ CRC_TABLES[6][((one >> 8) & 0xff) as usize]
+            ^ CRC_TABLES[5][((one >> 16) & 0xff) as usize]
+            ^ CRC_TABLES[4][(one >> 24) as usize]
+            ^ CRC_TABLES[3][(two & 0xff) as usize]
+            ^ CRC_TABLES[2][((two >> 8) & 0xff) as usize]
+            ^ CRC_TABLES[1][((two >> 16) & 0xff) as usize]
+            ^ CRC_TABLES[0][(two >> 24) as usize];
+    }
+    for &b in chunks.remainder() {
+        crc = (crc >> 8) ^ CRC_TABLES[0][((crc ^ b as u32) & 0xff) as usize];
+    }
+    !crc
+}
+
+// CRC_TABLES[0] is the usual byte at a time table, CRC_TABLES[k] is the same byte followed by k zero bytes
+// built at compile time so there's nothing to initialise
+const CRC_TABLES: [[u32; 256]; 8] = crc_tables();
+
+const fn crc_tables() -> [[u32; 256]; 8] {
+    let mut tables = [[0u32; 256]; 8];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+            bit += 1;
+        }
+        tables[0][i] = crc;
+        i += 1;
+    }
+    let mut k = 1;
+    while k < 8 {
+        let mut i = 0;
+        while i < 256 {
+            let prev = tables[k - 1][i];
+            tables[k][i] = (prev >> 8) ^ tables[0][(prev & 0xff) as usize];
+            i += 1;
+        }
+        k += 1;
+    }
+    tables
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc32_test() {
+        println!("\n\nTesting for CRC-32\n\n");
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+
+        // the tables agree with the plain bit at a time definition, for every length and wherever the input is split
+        let bitwise = |bytes: &[u8]| {
+            let mut crc = !0u32;
+            for &b in bytes {
+                crc ^= b as u32;
+                for _ in 0..8 {
+                    crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+                }
+            }
+            !crc
+        };
+        let bytes: Vec<u8> = (0..100u32).map(|i| (i * 167 + 13) as u8).collect();
+        for len in 0..bytes.len() {
+            assert_eq!(crc32(&bytes[..len]), bitwise(&bytes[..len]));
+            let (a, b) = bytes[..len].split_at(len / 3);
+            assert_eq!(crc32_update(crc32(a), b), bitwise(&bytes[..len]));
+        }
+    }
+}
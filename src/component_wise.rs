@@ -0,0 +1,170 @@
+use super::*;
+
+// Component by component utilities
+// Comparisons use PartialOrd, so a NaN component never wins a min/max - the other side is kept instead
+// (and for min_element/max_element/argmin/argmax a NaN is just skipped over unless it's the first component)
+
+impl<T: Numerical, const N: usize> ComponentWise<T, N> for NVec<T, N> {
+    fn abs(self) -> Self where T: num_traits::Signed {
+        self.map(|c| c.abs())
+    }
+
+    fn signum(self) -> Self where T: num_traits::Signed {
+        self.map(|c| c.signum())
+    }
+
+    fn floor(self) -> Self where T: num_traits::Float {
+        self.map(|c| c.floor())
+    }
+
+    fn ceil(self) -> Self where T: num_traits::Float {
+        self.map(|c| c.ceil())
+    }
+
+    // halfway cases go away from zero, like f64::round
+    fn round(self) -> Self where T: num_traits::Float {
+        self.map(|c| c.round())
+    }
+
+    fn min<U, V>(self, rhs: NVec<U, N>) -> NVec<V, N>
+    where
+        T: AsPrimitive<V> + Promote<U, Output = V>,
+        U: Numerical + AsPrimitive<V>,
+        V: Numerical + 'static,
+    {
+        self.zip_with(rhs, |a, b| {
+            let (a, b): (V, V) = (a.as_(), b.as_());
+            if b < a || is_nan(a) { b } else { a }
+        })
+    }
+
+    fn max<U, V>(self, rhs: NVec<U, N>) -> NVec<V, N>
+    where
+        T: AsPrimitive<V> + Promote<U, Output = V>,
+        U: Numerical + AsPrimitive<V>,
+        V: Numerical + 'static,
+    {
+        self.zip_with(rhs, |a, b| {
+            let (a, b): (V, V) = (a.as_(), b.as_());
+            if b > a || is_nan(a) { b } else { a }
+        })
+    }
+
+    fn clamp(self, lo: Self, hi: Self) -> Self {
+        NVec::new(core::array::from_fn(|i| clamp_one(self.components[i], lo.components[i], hi.components[i])))
+    }
+
+    fn clamp_scalar(self, lo: T, hi: T) -> Self {
+        self.map(|c| clamp_one(c, lo, hi))
+    }
+
+    fn sum(self) -> T where T: num_traits::Zero {
+        self.components.iter().fold(T::zero(), |acc, c| acc + *c)
+    }
+
+    fn product(self) -> T where T: num_traits::One {
+        self.components.iter().fold(T::one(), |acc, c| acc * *c)
+    }
+
+    fn min_element(self) -> T {
+        self.components[self.argmin()]
+    }
+
+    fn max_element(self) -> T {
+        self.components[self.argmax()]
+    }
+
+    // the first one if there's a tie
+    fn argmin(self) -> usize {
+        assert!(N > 0, "An empty NVec has no smallest component");
+        (1..N).fold(0, |best, i| if self.components[i] < self.components[best] { i } else { best })
+    }
+
+    fn argmax(self) -> usize {
+        assert!(N > 0, "An empty NVec has no largest component");
+        (1..N).fold(0, |best, i| if self.components[i] > self.components[best] { i } else { best })
+    }
+
+    fn map<U, F: FnMut(T) -> U>(self, f: F) -> NVec<U, N> {
+        NVec { components: self.components.map(f) }
+    }
+
+    fn zip_with<U, V, F: FnMut(T, U) -> V>(self, rhs: NVec<U, N>, mut f: F) -> NVec<V, N> {
+        let mut rhs = rhs.components.into_iter();
+        NVec { components: self.components.map(|a| f(a, rhs.next().unwrap())) }
+    }
+}
+
+// works for any PartialEq type since only NaN isn't equal to itself
+#[allow(clippy::eq_op)]
+fn is_nan<T: PartialEq>(x: T) -> bool {
+    x != x
+}
+
+// lo wins if the bounds are the wrong way round, rather than panicking like Ord::clamp
+fn clamp_one<T: Numerical>(c: T, lo: T, hi: T) -> T {
+    if c < lo {
+        lo
+    } else if c > hi {
+        hi
+    } else {
+        c
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_type_test() {
+        println!("\n\nTesting for component-wise utilities\n\n");
+        let a = NVec::new([-3, 7, 0, 2]);
+        let b = NVec::new([1, 5, -2, 2]);
+
+        assert_eq!(a.abs(), NVec::new([3, 7, 0, 2]));
+        assert_eq!(a.signum(), NVec::new([-1, 1, 0, 1]));
+        assert_eq!(a.min(b), NVec::new([-3, 5, -2, 2]));
+        assert_eq!(a.max(b), NVec::new([1, 7, 0, 2]));
+        assert_eq!(a.clamp(NVec::new([0; 4]), NVec::new([5; 4])), NVec::new([0, 5, 0, 2]));
+        assert_eq!(a.clamp_scalar(-1, 1), NVec::new([-1, 1, 0, 1]));
+
+        assert_eq!(a.sum(), 6);
+        assert_eq!(b.product(), -20);
+        assert_eq!(a.min_element(), -3);
+        assert_eq!(a.max_element(), 7);
+        assert_eq!(a.argmin(), 0);
+        assert_eq!(a.argmax(), 1);
+        assert_eq!(b.argmax(), 1);
+        assert_eq!(NVec::new([4u8, 9, 9]).argmax(), 1); //first of a tie
+
+        let f = NVec::new([1.5, -1.5, 2.49, -0.5]);
+        assert_eq!(f.floor(), NVec::new([1.0, -2.0, 2.0, -1.0]));
+        assert_eq!(f.ceil(), NVec::new([2.0, -1.0, 3.0, -0.0]));
+        assert_eq!(f.round(), NVec::new([2.0, -2.0, 2.0, -1.0]));
+        assert_eq!(f.abs().sum(), 5.99);
+    }
+
+    #[test]
+    fn casting_test() {
+        println!("\n\nTesting for component-wise utilities across types\n\n");
+        let a: NVec<i32, 3> = NVec::new([4, -5, 200]);
+        let b: NVec<f64, 3> = NVec::new([1.01, -2.65, 300.5]);
+
+        assert_eq!(a.min(b), NVec::new([1.01, -5.0, 200.0]));
+        assert_eq!(b.max(a), NVec::new([4.0, -2.65, 300.5]));
+        assert_eq!(NVec::new([1u8, 200]).max(NVec::new([-1i8, 3])), NVec::new([1i16, 200]));
+
+        // map and zip_with can change the component type
+        assert_eq!(a.map(|c| c as f32 * 0.5), NVec::new([2.0f32, -2.5, 100.0]));
+        assert_eq!(a.map(|c| c > 0), NVec { components: [true, false, true] });
+        assert_eq!(a.zip_with(b, |x, y| x as f64 > y), NVec { components: [true, false, false] });
+
+        // NaN never wins
+        let nan = NVec::new([f64::NAN, 1.0]);
+        assert_eq!(nan.min(NVec::new([0.0, 2.0])), NVec::new([0.0, 1.0]));
+        assert_eq!(NVec::new([0.0, 2.0]).max(nan), NVec::new([0.0, 2.0]));
+    }
+}
@@ -0,0 +1,151 @@
+use super::*;
+use num_traits::{ConstOne, ConstZero};
+
+// Compile time construction and arithmetic
+// NewNVec::new is a trait method so it can't be called in a const, these can
+// Trait ops (Add, Mul etc.) can't be const either, so the integer types get const_add/const_sub/const_dot/const_cross instead
+// e.g. const UP: NVec<i32, 3> = NVec::e::<2>(); const DOWN: NVec<i32, 3> = UP.const_neg();
+// Overflow in a const is a compile error, at runtime it behaves like the normal integer ops
+// They're separate inherent impls per type, so the component type has to be known before calling them (NVec::<i32, 3>::splat(1).const_add(..))
+
+impl<T: Copy, const N: usize> NVec<T, N> {
+    pub const fn from_array(components: [T; N]) -> Self {
+        NVec { components }
+    }
+
+    // every component set to value
+    pub const fn splat(value: T) -> Self {
+        NVec { components: [value; N] }
+    }
+}
+
+impl<T: Copy + ConstZero + ConstOne, const N: usize> NVec<T, N> {
+    pub const fn zeros() -> Self {
+        NVec { components: [T::ZERO; N] }
+    }
+
+    pub const fn ones() -> Self {
+        NVec { components: [T::ONE; N] }
+    }
+
+    // the I-th basis vector, I >= N is a compile error
+    pub const fn e<const I: usize>() -> Self {
+        const { assert!(I < N, "Basis vector index out of range for this dimension") };
+        let mut components = [T::ZERO; N];
+        components[I] = T::ONE;
+        NVec { components }
+    }
+}
+
+macro_rules! const_ops {
+    ($($type:ty), *) => {
+        $(
+            impl<const N: usize> NVec<$type, N> {
+                pub const fn const_add(self, rhs: Self) -> Self {
+                    let mut components = [0; N];
+                    let mut i = 0;
+                    while i < N { //no for loops in const fn
+                        components[i] = self.components[i] + rhs.components[i];
+                        i += 1;
+                    }
+                    NVec { components }
+                }
+
+                pub const fn const_sub(self, rhs: Self) -> Self {
+                    let mut components = [0; N];
+                    let mut i = 0;
+                    while i < N {
+                        components[i] = self.components[i] - rhs.components[i];
+                        i += 1;
+                    }
+                    NVec { components }
+                }
+
+                pub const fn const_scale(self, rhs: $type) -> Self {
+                    let mut components = [0; N];
+                    let mut i = 0;
+                    while i < N {
+                        components[i] = self.components[i] * rhs;
+                        i += 1;
+                    }
+                    NVec { components }
+                }
+
+                pub const fn const_dot(self, rhs: Self) -> $type {
+                    let mut acc = 0;
+                    let mut i = 0;
+                    while i < N {
+                        acc += self.components[i] * rhs.components[i];
+                        i += 1;
+                    }
+                    acc
+                }
+            }
+
+            impl NVec<$type, 3> {
+                pub const fn const_cross(self, rhs: Self) -> Self {
+                    let [a1, a2, a3] = self.components;
+                    let [b1, b2, b3] = rhs.components;
+                    NVec { components: [a2 * b3 - a3 * b2, a3 * b1 - a1 * b3, a1 * b2 - a2 * b1] }
+                }
+            }
+        )*
+    };
+}
+
+// negation only makes sense for the signed ones
+macro_rules! const_neg {
+    ($($type:ty), *) => {
+        $(
+            impl<const N: usize> NVec<$type, N> {
+                pub const fn const_neg(self) -> Self {
+                    let mut components = [0; N];
+                    let mut i = 0;
+                    while i < N {
+                        components[i] = -self.components[i];
+                        i += 1;
+                    }
+                    NVec { components }
+                }
+            }
+        )*
+    };
+}
+
+const_ops!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+const_neg!(i8, i16, i32, i64, i128);
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // all of these are worked out by the compiler
+    const X: NVec<i32, 3> = NVec::e::<0>();
+    const Y: NVec<i32, 3> = NVec::e::<1>();
+    const Z: NVec<i32, 3> = X.const_cross(Y);
+    const NEIGHBOURS: [NVec<i64, 2>; 4] = [NVec::e::<0>(), NVec::e::<1>(), NVec::<i64, 2>::e::<0>().const_neg(), NVec::<i64, 2>::e::<1>().const_neg()];
+    const DIAGONAL: NVec<u8, 4> = NVec::<u8, 4>::splat(3).const_add(NVec::ones()).const_sub(NVec::from_array([1, 1, 0, 0]));
+    const LENGTH_SQUARED: u8 = DIAGONAL.const_dot(DIAGONAL);
+
+    #[test]
+    fn const_test() {
+        println!("\n\nTesting for const construction and ops\n\n");
+        assert_eq!(Z, NVec::new([0, 0, 1]));
+        assert_eq!(X.const_add(Y).const_scale(2), NVec::new([2, 2, 0]));
+        assert_eq!(NEIGHBOURS[2], NVec::new([-1, 0]));
+        assert_eq!(DIAGONAL, NVec::new([3, 3, 4, 4]));
+        assert_eq!(LENGTH_SQUARED, 50);
+        assert_eq!(NVec::<f64, 2>::zeros(), NVec::new([0.0, 0.0]));
+        assert_eq!(NVec::<f32, 3>::e::<2>(), NVec::new([0.0, 0.0, 1.0]));
+
+        // the const versions agree with the trait ops
+        let a: NVec<i32, 3> = NVec::new([3, -4, 7]);
+        let b: NVec<i32, 3> = NVec::new([-2, 5, 1]);
+        assert_eq!(a.const_add(b), a + b);
+        assert_eq!(a.const_sub(b), a - b);
+        assert_eq!(a.const_dot(b), a * b);
+        assert_eq!(a.const_cross(b), a.cross(b));
+    }
+}
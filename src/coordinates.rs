@@ -0,0 +1,339 @@
+use super::*;
+use num_traits::Float;
+
+// Conversions between cartesian NVecs and other coordinate systems
+//     polar          NVec<T, 2>  <->  (r, theta)
+//     cylindrical    NVec<T, 3>  <->  (rho, phi, z)
+//     spherical      NVec<T, 3>  <->  (r, polar angle, azimuth)
+//     hyperspherical NVec<T, N>  <->  (r, N - 1 angles)
+// All angles go through Angle so there's never any doubt whether a number is in radians or degrees
+
+
+
+// Angles //
+
+// Stored in radians, but you have to say which you mean going in and coming out
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Default)]
+pub struct Angle<T> {
+    radians: T,
+}
+
+impl<T: Float> Angle<T> {
+    pub fn from_radians(radians: T) -> Self {
+        Angle { radians }
+    }
+
+    pub fn from_degrees(degrees: T) -> Self {
+        Angle { radians: degrees.to_radians() }
+    }
+
+    pub fn radians(&self) -> T {
+        self.radians
+    }
+
+    pub fn degrees(&self) -> T {
+        self.radians.to_degrees()
+    }
+
+    // wraps in to (-pi, pi]
+    pub fn normalized(&self) -> Self {
+        Angle::from_radians(self.radians.sin().atan2(self.radians.cos()))
+    }
+
+    pub fn sin(&self) -> T {
+        self.radians.sin()
+    }
+
+    pub fn cos(&self) -> T {
+        self.radians.cos()
+    }
+}
+
+impl<T: Float> Add for Angle<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Angle::from_radians(self.radians + rhs.radians)
+    }
+}
+
+impl<T: Float> Sub for Angle<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Angle::from_radians(self.radians - rhs.radians)
+    }
+}
+
+impl<T: Float> Mul<T> for Angle<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Angle::from_radians(self.radians * rhs)
+    }
+}
+
+impl<T: Float> Neg for Angle<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Angle::from_radians(-self.radians)
+    }
+}
+
+
+
+// Polar //
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Polar<T> {
+    pub r: T,
+    pub theta: Angle<T>, // anticlockwise from the +x axis
+}
+
+impl<T: Float + Numerical> Polar<T> {
+    pub fn new(r: T, theta: Angle<T>) -> Self {
+        Polar { r, theta }
+    }
+
+    pub fn from_cartesian(v: NVec<T, 2>) -> Self {
+        let [x, y] = v.components;
+        Polar::new(x.hypot(y), Angle::from_radians(y.atan2(x)))
+    }
+
+    pub fn to_cartesian(&self) -> NVec<T, 2> {
+        NVec::new([self.r * self.theta.cos(), self.r * self.theta.sin()])
+    }
+}
+
+
+
+// Cylindrical //
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Cylindrical<T> {
+    pub rho: T,          // distance from the z axis
+    pub phi: Angle<T>,   // anticlockwise from +x in the xy plane
+    pub z: T,
+}
+
+impl<T: Float + Numerical> Cylindrical<T> {
+    pub fn new(rho: T, phi: Angle<T>, z: T) -> Self {
+        Cylindrical { rho, phi, z }
+    }
+
+    pub fn from_cartesian(v: NVec<T, 3>) -> Self {
+        let [x, y, z] = v.components;
+        Cylindrical::new(x.hypot(y), Angle::from_radians(y.atan2(x)), z)
+    }
+
+    pub fn to_cartesian(&self) -> NVec<T, 3> {
+        NVec::new([self.rho * self.phi.cos(), self.rho * self.phi.sin(), self.z])
+    }
+}
+
+
+
+// Spherical //
+
+// Which of the two angles is called theta and which phi
+// Physics (ISO 80000-2): theta is the polar angle down from +z, phi is the azimuth
+// Maths: theta is the azimuth, phi is the polar angle
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SphericalConvention {
+    Physics,
+    Math,
+}
+
+// Stored by meaning rather than by name so the convention only matters at the edges
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Spherical<T> {
+    pub r: T,
+    pub polar: Angle<T>,    // down from +z, in [0, pi]
+    pub azimuth: Angle<T>,  // anticlockwise from +x in the xy plane
+}
+
+impl<T: Float + Numerical> Spherical<T> {
+    // theta and phi are read according to convention
+    pub fn new(r: T, theta: Angle<T>, phi: Angle<T>, convention: SphericalConvention) -> Self {
+        match convention {
+            SphericalConvention::Physics => Spherical { r, polar: theta, azimuth: phi },
+            SphericalConvention::Math => Spherical { r, polar: phi, azimuth: theta },
+        }
+    }
+
+    pub fn theta(&self, convention: SphericalConvention) -> Angle<T> {
+        match convention {
+            SphericalConvention::Physics => self.polar,
+            SphericalConvention::Math => self.azimuth,
+        }
+    }
+
+    pub fn phi(&self, convention: SphericalConvention) -> Angle<T> {
+        match convention {
+            SphericalConvention::Physics => self.azimuth,
+            SphericalConvention::Math => self.polar,
+        }
+    }
+
+    // the angle up from the xy plane, as used for latitude and sensor elevation
+    pub fn elevation(&self) -> Angle<T> {
+        Angle::from_radians(T::from(std::f64::consts::FRAC_PI_2).unwrap_or_else(T::zero) - self.polar.radians())
+    }
+
+    pub fn from_cartesian(v: NVec<T, 3>) -> Self {
+        let [x, y, z] = v.components;
+        let r = x.hypot(y).hypot(z);
+        Spherical {
+            r,
+            polar: Angle::from_radians(x.hypot(y).atan2(z)),
+            azimuth: Angle::from_radians(y.atan2(x)),
+        }
+    }
+
+    pub fn to_cartesian(&self) -> NVec<T, 3> {
+        let s = self.polar.sin();
+        NVec::new([self.r * s * self.azimuth.cos(), self.r * s * self.azimuth.sin(), self.r * self.polar.cos()])
+    }
+}
+
+
+
+// Hyperspherical //
+
+// The standard N dimensional generalisation:
+//     x_1 = r cos(a_1)
+//     x_2 = r sin(a_1) cos(a_2)
+//     ...
+//     x_N-1 = r sin(a_1) ... sin(a_N-2) cos(a_N-1)
+//     x_N   = r sin(a_1) ... sin(a_N-2) sin(a_N-1)
+// a_1 .. a_N-2 are in [0, pi] and the last one is in (-pi, pi]
+// For N = 2 this is just polar, and for N = 3 it's spherical but measured from the x axis rather than z
+// N - 1 can't be an array length with stable const generics, so the angles are a Vec
+// That's why the fields are private - new checks there are N - 1 of them and nothing can push/pop one afterwards
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hyperspherical<T, const N: usize> {
+    r: T,
+    angles: Vec<Angle<T>>,
+}
+
+impl<T: Float + Numerical, const N: usize> Hyperspherical<T, N> {
+    // panics if there aren't exactly N - 1 angles
+    pub fn new(r: T, angles: Vec<Angle<T>>) -> Self {
+        assert!(N >= 2, "Hyperspherical coordinates need at least 2 dimensions");
+        assert_eq!(angles.len(), N - 1, "Hyperspherical coordinates need N - 1 angles");
+        Hyperspherical { r, angles }
+    }
+
+    pub fn from_cartesian(v: NVec<T, N>) -> Self {
+        assert!(N >= 2, "Hyperspherical coordinates need at least 2 dimensions");
+        let x = v.components;
+
+        // tail[k] = sqrt(x_k^2 + ... + x_N^2), built from the end so each is one hypot
+        let mut tail = vec![T::zero(); N + 1];
+        for k in (0..N).rev() {
+            tail[k] = tail[k + 1].hypot(x[k]);
+        }
+
+        let mut angles: Vec<Angle<T>> = (0..N - 2).map(|k| Angle::from_radians(tail[k + 1].atan2(x[k]))).collect();
+        angles.push(Angle::from_radians(x[N - 1].atan2(x[N - 2])));
+
+        Hyperspherical { r: tail[0], angles }
+    }
+
+    pub fn r(&self) -> T {
+        self.r
+    }
+
+    // a_1 .. a_N-1, always N - 1 of them
+    pub fn angles(&self) -> &[Angle<T>] {
+        &self.angles
+    }
+
+    pub fn to_cartesian(&self) -> NVec<T, N> {
+        let mut components = [T::zero(); N];
+        let mut sin_product = self.r;
+
+        for (c, a) in components.iter_mut().zip(self.angles.iter()) {
+            *c = sin_product * a.cos();
+            sin_product = sin_product * a.sin();
+        }
+        components[N - 1] = sin_product;
+
+        NVec::new(components)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    #[test]
+    fn angle_test() {
+        println!("\n\nTesting for angles\n\n");
+        let a = Angle::from_degrees(180.0);
+        assert!((a.radians() - PI).abs() < 1e-15);
+        assert!((Angle::from_radians(FRAC_PI_2).degrees() - 90.0).abs() < 1e-12);
+        assert!(((a + a).normalized().radians()).abs() < 1e-12);
+        assert!(((Angle::from_degrees(270.0)).normalized().degrees() + 90.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn planar_and_cylindrical_test() {
+        println!("\n\nTesting for polar and cylindrical coordinates\n\n");
+        let p = Polar::from_cartesian(NVec::new([0.0, 2.0]));
+        assert_eq!(p.r, 2.0);
+        assert!((p.theta.degrees() - 90.0).abs() < 1e-12);
+        assert_nvec_approx_eq!(Polar::new(2.0, Angle::from_degrees(45.0)).to_cartesian(), NVec::new([2f64.sqrt(), 2f64.sqrt()]), epsilon = 1e-12);
+
+        let v = NVec::new([1.0, -1.0, 5.0]);
+        let c = Cylindrical::from_cartesian(v);
+        assert!((c.rho - 2f64.sqrt()).abs() < 1e-12);
+        assert!((c.phi.radians() + FRAC_PI_4).abs() < 1e-12);
+        assert_nvec_approx_eq!(c.to_cartesian(), v, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn spherical_test() {
+        println!("\n\nTesting for spherical coordinates\n\n");
+        let v = NVec::new([0.0, 3.0, 3.0]);
+        let s = Spherical::from_cartesian(v);
+
+        assert!((s.r - 18f64.sqrt()).abs() < 1e-12);
+        assert!((s.theta(SphericalConvention::Physics).degrees() - 45.0).abs() < 1e-12);
+        assert!((s.phi(SphericalConvention::Physics).degrees() - 90.0).abs() < 1e-12);
+        assert!((s.theta(SphericalConvention::Math).degrees() - 90.0).abs() < 1e-12);
+        assert!((s.phi(SphericalConvention::Math).degrees() - 45.0).abs() < 1e-12);
+        assert!((s.elevation().degrees() - 45.0).abs() < 1e-12);
+        assert_nvec_approx_eq!(s.to_cartesian(), v, epsilon = 1e-12);
+
+        // the same point written both ways
+        let physics = Spherical::new(1.0, Angle::from_degrees(90.0), Angle::from_degrees(0.0), SphericalConvention::Physics);
+        let math = Spherical::new(1.0, Angle::from_degrees(0.0), Angle::from_degrees(90.0), SphericalConvention::Math);
+        assert_eq!(physics, math);
+        assert_nvec_approx_eq!(physics.to_cartesian(), NVec::new([1.0, 0.0, 0.0]), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn hyperspherical_test() {
+        println!("\n\nTesting for hyperspherical coordinates\n\n");
+        let v = NVec::new([1.0, -2.0, 0.5, 3.0, -0.25]);
+        let h = Hyperspherical::from_cartesian(v);
+        assert_eq!(h.angles().len(), 4);
+        assert!((h.r() - f64::sqrt(v * v)).abs() < 1e-12);
+        assert_nvec_approx_eq!(h.to_cartesian(), v, epsilon = 1e-12);
+
+        // matches polar in 2D
+        let w = NVec::new([-1.0, 1.0]);
+        let h2 = Hyperspherical::from_cartesian(w);
+        let p = Polar::from_cartesian(w);
+        assert_eq!(h2.r(), p.r);
+        assert_eq!(h2.angles()[0], p.theta);
+
+        let axis: Hyperspherical<f64, 4> = Hyperspherical::new(2.0, vec![Angle::from_radians(FRAC_PI_2); 3]);
+        assert_nvec_approx_eq!(axis.to_cartesian(), NVec::new([0.0, 0.0, 0.0, 2.0]), epsilon = 1e-12);
+    }
+}
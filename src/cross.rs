@@ -45,17 +45,18 @@ mod tests {
     fn casting_test(){
         println!("\n\nTesting for cross product casting\n\n");
         let a: NVec<f64, 3> = NVec {
-            components: [1.01 as f64, -2.65 as f64, 3.4 as f64],
+            components: [1.01_f64, -2.65_f64, 3.4_f64],
         };
         let b: NVec<i32, 3> = NVec {
             components: [4, -5, 200],
         };
     
-        assert_eq!(
+        assert_nvec_approx_eq!(
             a.cross(b),
             NVec {
-                components: [-513.0f64, -188.4f64, 5.55f64] //if it's not working, check these
-            }
+                components: [-513.0f64, -188.4f64, 5.55f64]
+            },
+            relative = 1e-12
         );
         //TODO - get to work both ways
 
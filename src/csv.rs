@@ -0,0 +1,494 @@
+use super::*;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+// CSV/TSV files where each row is a vector
+// Reading is streaming - rows come out of an iterator one line at a time, so a file never has to fit in memory
+// A bad row gives an Err saying which line (and column) but doesn't stop the iterator, so callers can skip or bail out
+// Fields can be "quoted" (with "" for a quote inside), which headers sometimes need, but a row has to fit on one line
+//
+//     let reader = CsvReader::new(file, CsvOptions { has_header: true, columns: Columns::Names(vec!["x".into(), "y".into()]), ..Default::default() })?;
+//     for row in reader.nvecs::<f64, 2>() { ... }
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Columns {
+    #[default]
+    All,
+    Indices(Vec<usize>), //counting from 0, in the order they should go in to the vector
+    Names(Vec<String>),  //looked up in the header, so needs has_header
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvOptions {
+    pub delimiter: char,
+    pub has_header: bool,
+    pub columns: Columns,
+    pub trim: bool, //ignore whitespace around each field
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions { delimiter: ',', has_header: false, columns: Columns::All, trim: true }
+    }
+}
+
+impl CsvOptions {
+    pub fn tsv() -> Self {
+        CsvOptions { delimiter: '\t', ..Default::default() }
+    }
+}
+
+#[derive(Debug)]
+pub enum CsvError {
+    Io(io::Error),
+    // a row that couldn't be turned in to a vector, line counts from 1 including the header
+    // column is the field it went wrong in, from 1 like a spreadsheet (so Columns::Indices(vec![0]) is column 1), None when the row as a whole was wrong (e.g. the wrong number of fields)
+    // a Parse error inside has the byte offset in to the line, which counts from 0 like everywhere else
+    Row { line: usize, column: Option<usize>, error: NVecError },
+    // Columns::Names asked for something the header doesn't have (or there's no header)
+    MissingColumn(String),
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvError::Io(e) => write!(f, "{}", e),
+            CsvError::Row { line, column: Some(column), error } => write!(f, "line {}, column {}: {}", line, column, error),
+            CsvError::Row { line, column: None, error } => write!(f, "line {}: {}", line, error),
+            CsvError::MissingColumn(name) => write!(f, "no column named `{}` in the header", name),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CsvError::Io(e) => Some(e),
+            CsvError::Row { error, .. } => Some(error),
+            CsvError::MissingColumn(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for CsvError {
+    fn from(e: io::Error) -> Self {
+        CsvError::Io(e)
+    }
+}
+
+
+
+// Reading //
+
+pub struct CsvReader<R> {
+    reader: R,
+    options: CsvOptions,
+    header: Option<Vec<String>>,
+    selected: Option<Vec<usize>>, //the columns to take, None for all of them
+    width: Option<usize>,         //how many fields every row needs, once we know
+    line: usize,
+    done: bool,
+}
+
+impl<R: BufRead> CsvReader<R> {
+    // reads the header straight away (if there is one) so column names can be checked up front
+    pub fn new(reader: R, options: CsvOptions) -> Result<Self, CsvError> {
+        let mut csv = CsvReader { reader, options, header: None, selected: None, width: None, line: 0, done: false };
+
+        if csv.options.has_header {
+            if let Some(fields) = csv.next_fields().transpose()? {
+                csv.width = Some(fields.len());
+                csv.header = Some(fields.into_iter().map(|(_, f)| f).collect());
+            }
+        }
+
+        csv.selected = match &csv.options.columns {
+            Columns::All => None,
+            Columns::Indices(indices) => Some(indices.clone()),
+            Columns::Names(names) => {
+                let header = csv.header.as_deref().unwrap_or(&[]);
+                let lookup = |name: &String| header.iter().position(|h| h == name).ok_or_else(|| CsvError::MissingColumn(name.clone()));
+                Some(names.iter().map(lookup).collect::<Result<_, _>>()?)
+            }
+        };
+
+        Ok(csv)
+    }
+
+    pub fn header(&self) -> Option<&[String]> {
+        self.header.as_deref()
+    }
+
+    // Rows as NVec<T, N>, the selected columns (or every column) have to come to exactly N
+    pub fn nvecs<T, const N: usize>(self) -> NVecRows<R, T, N>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        NVecRows { csv: self, marker: std::marker::PhantomData }
+    }
+
+    // Rows as Vec<T> when the dimension isn't known until runtime
+    // Every row must be as wide as the header, or as the first row if there isn't one
+    pub fn rows<T>(self) -> Rows<R, T>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        Rows { csv: self, marker: std::marker::PhantomData }
+    }
+
+    // the next non-blank line split in to fields, each with the byte offset it started at
+    fn next_fields(&mut self) -> Option<Result<Vec<(usize, String)>, CsvError>> {
+        let mut buf = String::new();
+        loop {
+            if self.done {
+                return None;
+            }
+            buf.clear();
+            match self.reader.read_line(&mut buf) {
+                Ok(0) => {
+                    self.done = true;
+                    return None;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    self.done = true; //whatever went wrong with the reader, it won't be fixed by asking again
+                    return Some(Err(CsvError::Io(e)));
+                }
+            }
+            self.line += 1;
+
+            let line = buf.trim_end_matches(['\n', '\r']);
+            if line.trim().is_empty() {
+                continue;
+            }
+            return Some(split_fields(line, self.options.delimiter, self.options.trim).map_err(|error| self.row_error(None, error)));
+        }
+    }
+
+    fn row_error(&self, column: Option<usize>, error: NVecError) -> CsvError {
+        CsvError::Row { line: self.line, column, error }
+    }
+
+    // the next row parsed in to components, however many the columns give
+    fn next_row<T>(&mut self) -> Option<Result<Vec<T>, CsvError>>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let fields = match self.next_fields()? {
+            Ok(fields) => fields,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let expected = *self.width.get_or_insert(fields.len());
+        if fields.len() != expected {
+            return Some(Err(self.row_error(None, NVecError::DimensionMismatch { expected, found: fields.len() })));
+        }
+
+        let indices: Vec<usize> = match &self.selected {
+            Some(selected) => selected.clone(),
+            None => (0..fields.len()).collect(),
+        };
+
+        let mut row = Vec::with_capacity(indices.len());
+        for column in indices {
+            let Some((position, field)) = fields.get(column) else {
+                return Some(Err(self.row_error(Some(column + 1), NVecError::DimensionMismatch { expected: column + 1, found: fields.len() })));
+            };
+            match field.parse::<T>() {
+                Ok(c) => row.push(c),
+                Err(e) => {
+                    let message = format!("`{}` is not a valid {}: {}", field, std::any::type_name::<T>(), e);
+                    return Some(Err(self.row_error(Some(column + 1), NVecError::Parse { position: *position, message })));
+                }
+            }
+        }
+        Some(Ok(row))
+    }
+}
+
+pub struct NVecRows<R, T, const N: usize> {
+    csv: CsvReader<R>,
+    marker: std::marker::PhantomData<T>,
+}
+
+impl<R, T, const N: usize> NVecRows<R, T, N> {
+    pub fn header(&self) -> Option<&[String]> {
+        self.csv.header.as_deref()
+    }
+}
+
+impl<R: BufRead, T, const N: usize> Iterator for NVecRows<R, T, N>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    type Item = Result<NVec<T, N>, CsvError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let row = self.csv.next_row::<T>()?;
+        Some(row.and_then(|row| NVec::try_from(row).map_err(|error| self.csv.row_error(None, error))))
+    }
+}
+
+pub struct Rows<R, T> {
+    csv: CsvReader<R>,
+    marker: std::marker::PhantomData<T>,
+}
+
+impl<R, T> Rows<R, T> {
+    pub fn header(&self) -> Option<&[String]> {
+        self.csv.header.as_deref()
+    }
+}
+
+impl<R: BufRead, T> Iterator for Rows<R, T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    type Item = Result<Vec<T>, CsvError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.csv.next_row::<T>()
+    }
+}
+
+// Reads every row, stopping at the first error
+pub fn read_csv<R, T, const N: usize>(reader: R, options: CsvOptions) -> Result<Vec<NVec<T, N>>, CsvError>
+where
+    R: BufRead,
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    CsvReader::new(reader, options)?.nvecs().collect()
+}
+
+fn split_fields(line: &str, delimiter: char, trim: bool) -> Result<Vec<(usize, String)>, NVecError> {
+    let mut fields = Vec::new();
+    let mut chars = line.char_indices().peekable();
+    let mut start = 0;
+
+    loop {
+        // skip to the start of the field, so positions point at the value not the padding
+        while let Some(&(i, c)) = chars.peek() {
+            if trim && c.is_whitespace() && c != delimiter {
+                chars.next();
+                start = i + c.len_utf8();
+            } else {
+                break;
+            }
+        }
+
+        let mut field = String::new();
+        let mut end_of_line = true;
+        if let Some(&(quote_at, '"')) = chars.peek() {
+            chars.next();
+            loop {
+                match chars.next() {
+                    Some((_, '"')) if chars.peek().map(|&(_, c)| c) == Some('"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    Some((_, '"')) => break,
+                    Some((_, c)) => field.push(c),
+                    None => return Err(NVecError::Parse { position: quote_at, message: "unterminated quote".to_string() }),
+                }
+            }
+            // only padding is allowed between the closing quote and the delimiter
+            for (i, c) in chars.by_ref() {
+                if c == delimiter {
+                    end_of_line = false;
+                    break;
+                } else if !c.is_whitespace() {
+                    return Err(NVecError::Parse { position: i, message: format!("unexpected `{}` after a quoted field", c) });
+                }
+            }
+        } else {
+            for (_, c) in chars.by_ref() {
+                if c == delimiter {
+                    end_of_line = false;
+                    break;
+                }
+                field.push(c);
+            }
+            if trim {
+                field.truncate(field.trim_end().len());
+            }
+        }
+
+        fields.push((start, field));
+        if end_of_line {
+            return Ok(fields);
+        }
+        start = chars.peek().map_or(line.len(), |&(i, _)| i);
+    }
+}
+
+
+
+// Writing //
+
+pub struct CsvWriter<W: Write> {
+    writer: W,
+    delimiter: char,
+}
+
+impl<W: Write> CsvWriter<W> {
+    // only the delimiter matters for writing, the rest of the options are for reading
+    pub fn new(writer: W, options: &CsvOptions) -> Self {
+        CsvWriter { writer, delimiter: options.delimiter }
+    }
+
+    // the reader goes a line at a time, so a name with a line break in it couldn't be read back - that's an InvalidInput error
+    pub fn write_header<S: AsRef<str>>(&mut self, names: &[S]) -> io::Result<()> {
+        if let Some(name) = names.iter().map(AsRef::as_ref).find(|name| name.contains(['\n', '\r'])) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("column name {:?} has a line break in it", name)));
+        }
+        let fields: Vec<String> = names.iter().map(|name| quote(name.as_ref(), self.delimiter)).collect();
+        self.write_fields(fields)
+    }
+
+    // floats come out in their shortest exact form, so reading the file back gives the same bits
+    pub fn write_nvec<T: fmt::Display, const N: usize>(&mut self, v: &NVec<T, N>) -> io::Result<()> {
+        self.write_row(&v.components)
+    }
+
+    pub fn write_row<T: fmt::Display>(&mut self, row: &[T]) -> io::Result<()> {
+        self.write_fields(row.iter().map(|c| c.to_string()).collect())
+    }
+
+    fn write_fields(&mut self, fields: Vec<String>) -> io::Result<()> {
+        let mut delimiter = [0; 4];
+        let delimiter = self.delimiter.encode_utf8(&mut delimiter);
+        writeln!(self.writer, "{}", fields.join(delimiter))
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+// Writes an optional header then every vector, one per row
+pub fn write_csv<W, T, const N: usize>(writer: W, header: Option<&[&str]>, vecs: &[NVec<T, N>], options: &CsvOptions) -> io::Result<()>
+where
+    W: Write,
+    T: fmt::Display,
+{
+    let mut csv = CsvWriter::new(writer, options);
+    if let Some(header) = header {
+        csv.write_header(header)?;
+    }
+    for v in vecs {
+        csv.write_nvec(v)?;
+    }
+    csv.flush()
+}
+
+// header names only get quotes when they need them
+fn quote(field: &str, delimiter: char) -> String {
+    if field.contains(delimiter) || field.contains('"') || field.trim() != field {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn read_test() {
+        println!("\n\nTesting for reading CSV\n\n");
+        let data = "x,y,z\n1,2,3\n\n 4 , 5,6\r\n-7,8.5,9\n";
+        let options = CsvOptions { has_header: true, ..Default::default() };
+        let reader = CsvReader::new(Cursor::new(data), options.clone()).unwrap();
+        assert_eq!(reader.header(), Some(&["x".to_string(), "y".to_string(), "z".to_string()][..]));
+
+        let rows: Vec<_> = reader.nvecs::<f64, 3>().collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].as_ref().unwrap(), &NVec::new([1.0, 2.0, 3.0]));
+        assert_eq!(rows[1].as_ref().unwrap(), &NVec::new([4.0, 5.0, 6.0]));
+        assert_eq!(rows[2].as_ref().unwrap(), &NVec::new([-7.0, 8.5, 9.0]));
+
+        // the same file picking columns out by name, in a different order
+        let options = CsvOptions { columns: Columns::Names(vec!["z".into(), "x".into()]), ..options };
+        let picked: Vec<NVec<f64, 2>> = read_csv(Cursor::new(data), options).unwrap();
+        assert_eq!(picked, vec![NVec::new([3.0, 1.0]), NVec::new([6.0, 4.0]), NVec::new([9.0, -7.0])]);
+
+        // TSV with no header, by index, at runtime dimension
+        let tsv = "1\t2\t3\t4\n5\t6\t7\t8\n";
+        let rows: Vec<Vec<u8>> = CsvReader::new(Cursor::new(tsv), CsvOptions { columns: Columns::Indices(vec![3, 1, 0]), ..CsvOptions::tsv() })
+            .unwrap()
+            .rows()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(rows, vec![vec![4, 2, 1], vec![8, 6, 5]]);
+
+        // quoted fields, including the delimiter and quotes inside a header
+        let quoted = "\"a, b\",\"say \"\"hi\"\"\"\n\"1\", 2\n";
+        let reader = CsvReader::new(Cursor::new(quoted), CsvOptions { has_header: true, ..Default::default() }).unwrap();
+        assert_eq!(reader.header().unwrap(), &["a, b".to_string(), "say \"hi\"".to_string()]);
+        assert_eq!(reader.nvecs::<i32, 2>().next().unwrap().unwrap(), NVec::new([1, 2]));
+
+        let missing = CsvReader::new(Cursor::new(data), CsvOptions { has_header: true, columns: Columns::Names(vec!["w".into()]), ..Default::default() });
+        assert!(matches!(missing, Err(CsvError::MissingColumn(name)) if name == "w"));
+    }
+
+    #[test]
+    fn row_error_test() {
+        println!("\n\nTesting for CSV row errors\n\n");
+        let data = "1,2,3\n4,x,6\n7,8\n10,11,12\n13,\"14\n";
+        let rows: Vec<_> = CsvReader::new(Cursor::new(data), CsvOptions::default()).unwrap().nvecs::<i32, 3>().collect();
+        assert_eq!(rows.len(), 5);
+        assert!(rows[0].is_ok());
+        match &rows[1] {
+            Err(CsvError::Row { line: 2, column: Some(2), error: NVecError::Parse { position: 2, .. } }) => {}
+            other => panic!("{:?}", other),
+        }
+        assert!(matches!(&rows[2], Err(CsvError::Row { line: 3, column: None, error: NVecError::DimensionMismatch { expected: 3, found: 2 } })));
+        assert_eq!(rows[3].as_ref().unwrap(), &NVec::new([10, 11, 12])); //carries on after bad rows
+        assert!(matches!(&rows[4], Err(CsvError::Row { line: 5, error: NVecError::Parse { position: 3, .. }, .. })));
+        assert_eq!(rows[1].as_ref().unwrap_err().to_string(), "line 2, column 2: parse error at position 2: `x` is not a valid i32: invalid digit found in string");
+
+        // every column is fine but there are more of them than the NVec has room for
+        let wide: Result<Vec<NVec<i32, 2>>, _> = read_csv(Cursor::new("1,2,3\n"), CsvOptions::default());
+        assert!(matches!(wide, Err(CsvError::Row { line: 1, column: None, error: NVecError::DimensionMismatch { expected: 2, found: 3 } })));
+    }
+
+    #[test]
+    fn round_trip_test() {
+        println!("\n\nTesting for CSV write then read\n\n");
+        let vecs = vec![NVec::new([0.1, -2.5e-300, 3.0]), NVec::new([1.0 / 3.0, f64::MAX, -0.0])];
+
+        for options in [CsvOptions::default(), CsvOptions::tsv(), CsvOptions { delimiter: ';', ..Default::default() }] {
+            let mut out = Vec::new();
+            write_csv(&mut out, Some(&["x", "y;z", "w"]), &vecs, &options).unwrap();
+            let read: Vec<NVec<f64, 3>> = read_csv(Cursor::new(&out), CsvOptions { has_header: true, ..options.clone() }).unwrap();
+            assert_eq!(read, vecs);
+        }
+
+        let mut csv = CsvWriter::new(Vec::new(), &CsvOptions::default());
+        csv.write_header(&["a", "b,c", " d"]).unwrap();
+        csv.write_nvec(&NVec::new([1u8, 2, 3])).unwrap();
+        csv.write_row(&[4, 5]).unwrap();
+        assert_eq!(String::from_utf8(csv.into_inner()).unwrap(), "a,\"b,c\",\" d\"\n1,2,3\n4,5\n");
+
+        // names that couldn't be read back are turned away before anything is written
+        let mut csv = CsvWriter::new(Vec::new(), &CsvOptions::default());
+        assert_eq!(csv.write_header(&["a", "b\nc"]).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        assert!(write_csv(Vec::new(), Some(&["x\r"]), &[NVec::new([1])], &CsvOptions::default()).is_err());
+        assert!(csv.into_inner().is_empty());
+    }
+}
@@ -0,0 +1,216 @@
+use super::*;
+use num_traits::Float;
+
+// Dual quaternions for rigid body transforms - a rotation and a translation in one
+// real + e * dual, where e^2 = 0
+// For a unit dual quaternion the real part is the rotation and the dual part is t * real / 2 (t the translation as a pure quaternion)
+// Composition is just multiplication, the same as with plain quaternions, and ScLERP moves along a screw so interpolation is smooth
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DualQuaternion<T> {
+    pub real: Quaternion<T>,
+    pub dual: Quaternion<T>,
+}
+
+impl<T> DualQuaternion<T>
+where
+    T: Float + Numerical + AsPrimitive<T> + Promote<T, Output = T>, // so we can use the NVec ops on the vector parts
+{
+    pub fn new(real: Quaternion<T>, dual: Quaternion<T>) -> Self {
+        DualQuaternion { real, dual }
+    }
+
+    pub fn identity() -> Self {
+        DualQuaternion::new(Quaternion::identity(), Quaternion::new(T::zero(), T::zero(), T::zero(), T::zero()))
+    }
+
+    // rotate first, then translate
+    pub fn from_rotation_translation(rotation: Quaternion<T>, translation: NVec<T, 3>) -> Self {
+        let half = T::one() / (T::one() + T::one());
+        let real = rotation.normalize();
+
+        DualQuaternion::new(real, (Quaternion::from_vector(translation) * real).scale(half))
+    }
+
+    pub fn from_rotation(rotation: Quaternion<T>) -> Self {
+        DualQuaternion::from_rotation_translation(rotation, NVec::new([T::zero(); 3]))
+    }
+
+    pub fn from_translation(translation: NVec<T, 3>) -> Self {
+        DualQuaternion::from_rotation_translation(Quaternion::identity(), translation)
+    }
+
+    pub fn rotation(&self) -> Quaternion<T> {
+        self.real
+    }
+
+    // t = 2 * dual * real*
+    pub fn translation(&self) -> NVec<T, 3> {
+        let t = (self.dual * self.real.conjugate()).scale(T::one() + T::one());
+        t.vector()
+    }
+
+    pub fn to_rotation_translation(&self) -> (Quaternion<T>, NVec<T, 3>) {
+        (self.rotation(), self.translation())
+    }
+
+    pub fn conjugate(&self) -> Self {
+        DualQuaternion::new(self.real.conjugate(), self.dual.conjugate())
+    }
+
+    // rescales so the real part is unit length, and removes any drift that would make the dual part stop describing a translation
+    pub fn normalize(&self) -> Self {
+        let n = self.real.norm();
+        if n == T::zero() {
+            return DualQuaternion::identity();
+        }
+
+        let real = self.real.scale(T::one() / n);
+        let dual = self.dual.scale(T::one() / n);
+
+        DualQuaternion::new(real, dual - real.scale(real.dot(&dual)))
+    }
+
+    // None when the real part is zero since there's no rotation to undo
+    pub fn inverse(&self) -> Option<Self> {
+        let real_inv = self.real.inverse()?;
+
+        // (r + e d)^-1 = r^-1 - e r^-1 d r^-1
+        Some(DualQuaternion::new(real_inv, -(real_inv * self.dual * real_inv)))
+    }
+
+    // points get the full transform
+    pub fn transform_point(&self, p: NVec<T, 3>) -> NVec<T, 3> {
+        self.real.rotate(p) + self.translation()
+    }
+
+    // directions only get rotated - translating a direction doesn't mean anything
+    pub fn transform_vector(&self, v: NVec<T, 3>) -> NVec<T, 3> {
+        self.real.rotate(v)
+    }
+
+    // raises a unit dual quaternion to a real power by scaling its screw parameters (angle and distance along the axis)
+    pub fn powf(&self, t: T) -> Self {
+        let one = T::one();
+        let two = one + one;
+
+        let w = self.real.w.max(-one).min(one);
+        let half_angle = w.acos();
+        let s = half_angle.sin();
+
+        if s.abs() < T::epsilon().sqrt() {
+            // no rotation to speak of so it's a pure translation - just scale that
+            return DualQuaternion::from_rotation_translation(self.real, self.translation() * t);
+        }
+
+        let axis = self.real.vector() * (one / s);
+        let d = -two * self.dual.w / s; //distance moved along the axis
+        let moment = (self.dual.vector() - axis * (d / two * half_angle.cos())) * (one / s);
+
+        let (new_s, new_c) = (half_angle * t).sin_cos();
+        let new_d = d * t;
+
+        DualQuaternion::new(
+            Quaternion::from_parts(new_c, axis * new_s),
+            Quaternion::from_parts(-new_d / two * new_s, moment * new_s + axis * (new_d / two * new_c)),
+        )
+    }
+
+    // screw linear interpolation - constant speed rotation and translation about a single screw axis from self (t = 0) to other (t = 1)
+    pub fn sclerp(&self, other: &Self, t: T) -> Self {
+        let other = if self.real.dot(&other.real) < T::zero() { -*other } else { *other }; //take the short way round
+
+        let diff = self.conjugate() * other; //conjugate is the inverse for unit dual quaternions
+        (*self * diff.powf(t)).normalize()
+    }
+}
+
+
+
+// Operations //
+
+// composes transforms - (a * b) applies b first then a
+impl<T: Float> Mul for DualQuaternion<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        DualQuaternion {
+            real: self.real * rhs.real,
+            dual: self.real * rhs.dual + self.dual * rhs.real,
+        }
+    }
+}
+
+// dq * p transforms p as a point
+impl<T> Mul<NVec<T, 3>> for DualQuaternion<T>
+where
+    T: Float + Numerical + AsPrimitive<T> + Promote<T, Output = T>,
+{
+    type Output = NVec<T, 3>;
+
+    fn mul(self, rhs: NVec<T, 3>) -> NVec<T, 3> {
+        self.transform_point(rhs)
+    }
+}
+
+impl<T: Float> Neg for DualQuaternion<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        DualQuaternion { real: -self.real, dual: -self.dual }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn transform_test() {
+        println!("\n\nTesting for dual quaternion transforms\n\n");
+        let r = Quaternion::from_axis_angle(NVec::new([0.0, 0.0, 1.0]), FRAC_PI_2);
+        let t = NVec::new([1.0, 2.0, 3.0]);
+        let dq = DualQuaternion::from_rotation_translation(r, t);
+        let p = NVec::new([1.0, 0.0, 0.0]);
+
+        assert_nvec_approx_eq!(dq.transform_point(p), NVec::new([1.0, 3.0, 3.0]), epsilon = 1e-12);
+        assert_nvec_approx_eq!(dq * p, NVec::new([1.0, 3.0, 3.0]), epsilon = 1e-12);
+        assert_nvec_approx_eq!(dq.transform_vector(p), NVec::new([0.0, 1.0, 0.0]), epsilon = 1e-12);
+
+        let (r2, t2) = dq.to_rotation_translation();
+        assert_eq!(r2, r);
+        assert_nvec_approx_eq!(t2, t, epsilon = 1e-12);
+
+        let inv = dq.inverse().unwrap();
+        assert_nvec_approx_eq!(inv.transform_point(dq.transform_point(p)), p, epsilon = 1e-12);
+
+        // composing applies the right hand side first
+        let shift = DualQuaternion::from_translation(NVec::new([0.0, 0.0, -3.0]));
+        assert_nvec_approx_eq!((shift * dq).transform_point(p), NVec::new([1.0, 3.0, 0.0]), epsilon = 1e-12);
+        assert_nvec_approx_eq!((dq * shift).transform_point(p), NVec::new([1.0, 3.0, 0.0]), epsilon = 1e-12);
+        assert_nvec_approx_eq!((dq * DualQuaternion::from_translation(NVec::new([1.0, 0.0, 0.0]))).transform_point(p), NVec::new([1.0, 4.0, 3.0]), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn sclerp_test() {
+        println!("\n\nTesting for dual quaternion ScLERP\n\n");
+        let a = DualQuaternion::identity();
+        let b = DualQuaternion::from_rotation_translation(Quaternion::from_axis_angle(NVec::new([0.0, 0.0, 1.0]), FRAC_PI_2), NVec::new([0.0, 0.0, 4.0]));
+        let p = NVec::new([1.0, 0.0, 0.0]);
+
+        assert_nvec_approx_eq!(a.sclerp(&b, 0.0).transform_point(p), p, epsilon = 1e-12);
+        assert_nvec_approx_eq!(a.sclerp(&b, 1.0).transform_point(p), b.transform_point(p), epsilon = 1e-12);
+
+        // a screw about the z axis - half way is half the angle and half the height
+        let half = a.sclerp(&b, 0.5);
+        let s = 0.5f64.sqrt();
+        assert_nvec_approx_eq!(half.transform_point(p), NVec::new([s, s, 2.0]), epsilon = 1e-12);
+
+        // pure translations interpolate linearly
+        let c = DualQuaternion::from_translation(NVec::new([2.0, -4.0, 6.0]));
+        assert_nvec_approx_eq!(a.sclerp(&c, 0.25).translation(), NVec::new([0.5, -1.0, 1.5]), epsilon = 1e-12);
+    }
+}
@@ -48,7 +48,7 @@ mod tests {
     fn casting_test(){
         println!("\n\nTesting for elementwise multiplication\n\n");
         let a: NVec<f64, 3> = NVec {
-            components: [1.01 as f64, -2.65 as f64, 3.4 as f64],
+            components: [1.01_f64, -2.65_f64, 3.4_f64],
         };
         let b: NVec<i32, 3> = NVec {
             components: [4, -5, 200],
@@ -0,0 +1,139 @@
+use super::*;
+use std::fmt;
+
+// The crate-wide error type
+// Anything that can fail without it being a programming mistake gives back a Result<_, NVecError>
+// rather than panicking or quietly handing back garbage (wrapped integers, infinities, NaNs)
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NVecError {
+    // normalising (or otherwise dividing by the length of) a zero vector
+    ZeroLength,
+    // the wrong number of components, e.g. building an NVec<T, 3> from a slice of 4
+    DimensionMismatch { expected: usize, found: usize },
+    // an integer result didn't fit, at this component (or term of a sum)
+    Overflow { index: usize },
+    // a float result was infinite or NaN, at this component (or term of a sum)
+    NonFinite { index: usize },
+    // text that couldn't be read as an NVec, position is the byte offset the problem was found at
+    Parse { position: usize, message: String },
+    // a matrix/transform with no inverse
+    Singular,
+}
+
+impl fmt::Display for NVecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NVecError::ZeroLength => write!(f, "vector has zero length"),
+            NVecError::DimensionMismatch { expected, found } => write!(f, "expected {} components but found {}", expected, found),
+            NVecError::Overflow { index } => write!(f, "integer overflow at component {}", index),
+            NVecError::NonFinite { index } => write!(f, "non-finite result at component {}", index),
+            NVecError::Parse { position, message } => write!(f, "parse error at position {}: {}", position, message),
+            NVecError::Singular => write!(f, "matrix is singular"),
+        }
+    }
+}
+
+impl std::error::Error for NVecError {}
+
+impl<T> From<CastError<T>> for NVecError {
+    fn from(e: CastError<T>) -> Self {
+        NVecError::Overflow { index: e.index }
+    }
+}
+
+
+
+// Checked component arithmetic //
+// What the try_ ops are built on - integers use checked_*, floats check the result is finite
+// index is passed in so the error can say where it went wrong
+
+pub trait CheckedNumerical: Numerical {
+    fn try_add(self, rhs: Self, index: usize) -> Result<Self, NVecError>;
+    fn try_sub(self, rhs: Self, index: usize) -> Result<Self, NVecError>;
+    fn try_mul(self, rhs: Self, index: usize) -> Result<Self, NVecError>;
+}
+
+macro_rules! checked_int {
+    ($($type:ty), *) => {
+        $(
+            impl CheckedNumerical for $type {
+                fn try_add(self, rhs: Self, index: usize) -> Result<Self, NVecError> {
+                    self.checked_add(rhs).ok_or(NVecError::Overflow { index })
+                }
+
+                fn try_sub(self, rhs: Self, index: usize) -> Result<Self, NVecError> {
+                    self.checked_sub(rhs).ok_or(NVecError::Overflow { index })
+                }
+
+                fn try_mul(self, rhs: Self, index: usize) -> Result<Self, NVecError> {
+                    self.checked_mul(rhs).ok_or(NVecError::Overflow { index })
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! checked_float {
+    ($($type:ty), *) => {
+        $(
+            impl CheckedNumerical for $type {
+                fn try_add(self, rhs: Self, index: usize) -> Result<Self, NVecError> {
+                    finite(self + rhs, index)
+                }
+
+                fn try_sub(self, rhs: Self, index: usize) -> Result<Self, NVecError> {
+                    finite(self - rhs, index)
+                }
+
+                fn try_mul(self, rhs: Self, index: usize) -> Result<Self, NVecError> {
+                    finite(self * rhs, index)
+                }
+            }
+        )*
+    };
+}
+
+checked_int!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+checked_float!(f32, f64);
+
+// Builds an NVec one component at a time, stopping at the first error
+pub(crate) fn try_components<V, const N: usize>(mut f: impl FnMut(usize) -> Result<V, NVecError>) -> Result<NVec<V, N>, NVecError> {
+    let mut components = Vec::with_capacity(N);
+    for i in 0..N {
+        components.push(f(i)?);
+    }
+    Ok(NVec::try_from(components).unwrap_or_else(|_| unreachable!())) //one pushed per component
+}
+
+fn finite<T: num_traits::Float>(x: T, index: usize) -> Result<T, NVecError> {
+    if x.is_finite() {
+        Ok(x)
+    } else {
+        Err(NVecError::NonFinite { index })
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_test() {
+        println!("\n\nTesting for NVecError\n\n");
+        assert_eq!(NVecError::DimensionMismatch { expected: 3, found: 4 }.to_string(), "expected 3 components but found 4");
+        assert_eq!(NVecError::Parse { position: 7, message: "expected a number".to_string() }.to_string(), "parse error at position 7: expected a number");
+        assert_eq!(NVecError::from(NVec::new([1, 300]).try_cast::<u8>().unwrap_err()), NVecError::Overflow { index: 1 });
+
+        // usable behind the std error trait
+        let boxed: Box<dyn std::error::Error> = Box::new(NVecError::Singular);
+        assert_eq!(boxed.to_string(), "matrix is singular");
+
+        assert_eq!(250u8.try_add(10, 2), Err(NVecError::Overflow { index: 2 }));
+        assert_eq!(0u8.try_sub(1, 0), Err(NVecError::Overflow { index: 0 }));
+        assert_eq!(1e300f64.try_mul(1e300, 1), Err(NVecError::NonFinite { index: 1 }));
+        assert_eq!(1.5f32.try_add(1.0, 0), Ok(2.5));
+    }
+}
@@ -0,0 +1,311 @@
+use super::*;
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+// Floats that are guaranteed never to be NaN or infinite
+// Plain floats let a NaN slip in and quietly spread through every dot product and magnitude after it
+// Since a Finite can never hold one it can have a total order, so it gets Eq, Ord and Hash,
+// which means NVec<Finite<f64>, N> can be sorted, deduplicated and used as a HashMap/BTreeMap key
+//
+// Every result is checked, two ways to choose from:
+//     + - * /               panic if the result isn't finite (like integer overflow in debug, but in every build,
+//                           since letting a NaN in would break Eq/Ord/Hash for everything it touches)
+//     try_add/try_sub/...   give back Err(NVecError::NonFinite) instead, and the NVec try_ ops use these too
+
+#[derive(Copy, Clone, Default, PartialEq, PartialOrd)]
+pub struct Finite<T>(T);
+
+impl<T: Copy> Finite<T> {
+    pub fn get(self) -> T {
+        self.0
+    }
+}
+
+// Same problem as Interval - no way to say "for T in {f32, f64}", so a macro it is
+// (which also means Finite::new etc need the type spelling out, e.g. Finite::<f64>::new(1.0))
+macro_rules! finite {
+    ($($type:ty), *) => {
+        $(
+            impl Finite<$type> {
+                pub const ZERO: Self = Finite(0.0);
+                pub const ONE: Self = Finite(1.0);
+                pub const MAX: Self = Finite(<$type>::MAX);
+                pub const MIN: Self = Finite(<$type>::MIN);
+
+                pub fn new(x: $type) -> Result<Self, NVecError> {
+                    Self::checked(x, 0)
+                }
+
+                fn checked(x: $type, index: usize) -> Result<Self, NVecError> {
+                    if x.is_finite() {
+                        Ok(Finite(x))
+                    } else {
+                        Err(NVecError::NonFinite { index })
+                    }
+                }
+
+                // the panicking path for the operators
+                fn expect_finite(x: $type, op: &str) -> Self {
+                    match Self::checked(x, 0) {
+                        Ok(f) => f,
+                        Err(_) => panic!("Finite<{}> {} gave a non-finite result ({})", stringify!($type), op, x),
+                    }
+                }
+
+                pub fn try_add(self, rhs: Self) -> Result<Self, NVecError> {
+                    Self::checked(self.0 + rhs.0, 0)
+                }
+
+                pub fn try_sub(self, rhs: Self) -> Result<Self, NVecError> {
+                    Self::checked(self.0 - rhs.0, 0)
+                }
+
+                pub fn try_mul(self, rhs: Self) -> Result<Self, NVecError> {
+                    Self::checked(self.0 * rhs.0, 0)
+                }
+
+                // dividing by zero is the usual way in to infinity and NaN, so this is the one to reach for
+                pub fn try_div(self, rhs: Self) -> Result<Self, NVecError> {
+                    Self::checked(self.0 / rhs.0, 0)
+                }
+
+                pub fn abs(self) -> Self {
+                    Finite(self.0.abs())
+                }
+
+                // a negative input is the only way to get NaN out of a sqrt
+                pub fn try_sqrt(self) -> Result<Self, NVecError> {
+                    Self::checked(self.0.sqrt(), 0)
+                }
+            }
+
+            impl TryFrom<$type> for Finite<$type> {
+                type Error = NVecError;
+
+                fn try_from(x: $type) -> Result<Self, NVecError> {
+                    Finite::<$type>::new(x)
+                }
+            }
+
+            impl From<Finite<$type>> for $type {
+                fn from(x: Finite<$type>) -> Self {
+                    x.0
+                }
+            }
+
+            impl std::fmt::Debug for Finite<$type> {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    write!(f, "{:?}", self.0)
+                }
+            }
+
+            impl std::fmt::Display for Finite<$type> {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    std::fmt::Display::fmt(&self.0, f) //passes the precision etc through
+                }
+            }
+
+            // no NaN means partial_cmp always has an answer
+            impl Eq for Finite<$type> {}
+
+            impl Ord for Finite<$type> {
+                fn cmp(&self, other: &Self) -> Ordering {
+                    self.0.partial_cmp(&other.0).unwrap_or_else(|| unreachable!())
+                }
+            }
+
+            // -0 == 0, so they have to hash the same too
+            impl Hash for Finite<$type> {
+                fn hash<H: Hasher>(&self, state: &mut H) {
+                    let x = if self.0 == 0.0 { 0.0 } else { self.0 };
+                    x.to_bits().hash(state);
+                }
+            }
+
+            impl Add for Finite<$type> {
+                type Output = Self;
+
+                fn add(self, rhs: Self) -> Self {
+                    Self::expect_finite(self.0 + rhs.0, "addition")
+                }
+            }
+
+            impl Sub for Finite<$type> {
+                type Output = Self;
+
+                fn sub(self, rhs: Self) -> Self {
+                    Self::expect_finite(self.0 - rhs.0, "subtraction")
+                }
+            }
+
+            impl Mul for Finite<$type> {
+                type Output = Self;
+
+                fn mul(self, rhs: Self) -> Self {
+                    Self::expect_finite(self.0 * rhs.0, "multiplication")
+                }
+            }
+
+            impl Div for Finite<$type> {
+                type Output = Self;
+
+                fn div(self, rhs: Self) -> Self {
+                    Self::expect_finite(self.0 / rhs.0, "division")
+                }
+            }
+
+            impl Neg for Finite<$type> {
+                type Output = Self;
+
+                fn neg(self) -> Self {
+                    Finite(-self.0)
+                }
+            }
+
+            impl num_traits::Zero for Finite<$type> {
+                fn zero() -> Self {
+                    Self::ZERO
+                }
+
+                fn is_zero(&self) -> bool {
+                    self.0 == 0.0
+                }
+            }
+
+            impl num_traits::One for Finite<$type> {
+                fn one() -> Self {
+                    Self::ONE
+                }
+            }
+
+            impl Numerical for Finite<$type> {}
+
+            impl CheckedNumerical for Finite<$type> {
+                fn try_add(self, rhs: Self, index: usize) -> Result<Self, NVecError> {
+                    Self::checked(self.0 + rhs.0, index)
+                }
+
+                fn try_sub(self, rhs: Self, index: usize) -> Result<Self, NVecError> {
+                    Self::checked(self.0 - rhs.0, index)
+                }
+
+                fn try_mul(self, rhs: Self, index: usize) -> Result<Self, NVecError> {
+                    Self::checked(self.0 * rhs.0, index)
+                }
+            }
+
+            impl AsPrimitive<Finite<$type>> for Finite<$type> {
+                fn as_(self) -> Finite<$type> {
+                    self
+                }
+            }
+
+            impl AsPrimitive<$type> for Finite<$type> {
+                fn as_(self) -> $type {
+                    self.0
+                }
+            }
+
+            promote_self!(Finite<$type>, Finite<$type> => Finite<$type>);
+
+            impl<const N: usize> NVec<$type, N> {
+                // Err at the first component that's NaN or infinite
+                pub fn to_finite(self) -> Result<NVec<Finite<$type>, N>, NVecError> {
+                    error::try_components(|i| Finite::<$type>::checked(self.components[i], i))
+                }
+            }
+        )*
+    };
+}
+
+finite!(f32, f64);
+
+// every f32 is an f64, and a finite one stays finite
+impl From<Finite<f32>> for f64 {
+    fn from(x: Finite<f32>) -> Self {
+        x.0 as f64 // needed for magnitude
+    }
+}
+
+impl AsPrimitive<Finite<f64>> for Finite<f32> {
+    fn as_(self) -> Finite<f64> {
+        Finite(self.0 as f64)
+    }
+}
+
+promote!(Finite<f32>, Finite<f64> => Finite<f64>);
+
+impl<T: Copy, const N: usize> NVec<Finite<T>, N> {
+    pub fn into_inner(self) -> NVec<T, N> {
+        NVec { components: self.components.map(Finite::get) }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeMap, HashMap, HashSet};
+
+    fn fin<const N: usize>(x: [f64; N]) -> NVec<Finite<f64>, N> {
+        NVec::new(x).to_finite().unwrap()
+    }
+
+    #[test]
+    fn construction_test() {
+        println!("\n\nTesting for Finite construction and checked ops\n\n");
+        assert_eq!(Finite::<f64>::new(1.5).map(Finite::get), Ok(1.5));
+        assert_eq!(Finite::<f64>::new(f64::NAN), Err(NVecError::NonFinite { index: 0 }));
+        assert!(Finite::<f32>::try_from(f32::INFINITY).is_err());
+        assert_eq!(NVec::new([1.0, f64::NEG_INFINITY, 2.0]).to_finite(), Err(NVecError::NonFinite { index: 1 }));
+
+        let one = Finite::<f64>::ONE;
+        assert_eq!(one.try_div(Finite::<f64>::ZERO), Err(NVecError::NonFinite { index: 0 }));
+        assert_eq!(Finite::<f64>::MAX.try_add(Finite::<f64>::MAX), Err(NVecError::NonFinite { index: 0 }));
+        assert_eq!((-one).try_sqrt(), Err(NVecError::NonFinite { index: 0 }));
+        assert_eq!((one + one).get(), 2.0);
+
+        // the vector ops all work, with the NVec try_ ops as the Result mode
+        let a = fin([1.0, 2.0, 3.0]);
+        let b = fin([4.0, -5.0, 6.0]);
+        assert_eq!((a + b).into_inner(), NVec::new([5.0, -3.0, 9.0]));
+        assert_eq!((a * b).get(), 12.0);
+        assert_eq!(a.cross(b).into_inner(), NVec::new([27.0, 6.0, -13.0]));
+        assert_eq!(fin([3.0, 4.0]).mag(), 5.0);
+        assert_eq!(fin([f64::MAX, 1.0]).try_add(fin([f64::MAX, 1.0])), Err::<NVec<Finite<f64>, 2>, _>(NVecError::NonFinite { index: 0 }));
+        assert_eq!(fin([1.0, 1e200]).try_dot(fin([1.0, 1e200])), Err::<Finite<f64>, _>(NVecError::NonFinite { index: 1 }));
+
+        let c: NVec<Finite<f32>, 2> = NVec::new([1.5f32, 2.0]).to_finite().unwrap();
+        let d: NVec<Finite<f64>, 2> = c + fin([0.5, 0.5]);
+        assert_eq!(d.into_inner(), NVec::new([2.0, 2.5]));
+        assert_eq!(format!("{:.2}", d), "[2.00, 2.50]");
+    }
+
+    #[test]
+    #[should_panic(expected = "Finite<f64> division gave a non-finite result")]
+    fn panic_test() {
+        let _ = Finite::<f64>::ONE / Finite::<f64>::ZERO;
+    }
+
+    #[test]
+    fn ordering_test() {
+        println!("\n\nTesting for Finite as a key and sort order\n\n");
+        let mut points = vec![fin([1.0, 2.0]), fin([-3.0, 0.5]), fin([1.0, -2.0]), fin([0.0, 0.0])];
+        points.sort();
+        assert_eq!(points, vec![fin([-3.0, 0.5]), fin([0.0, 0.0]), fin([1.0, -2.0]), fin([1.0, 2.0])]);
+        assert_eq!(points.iter().max(), Some(&fin([1.0, 2.0])));
+
+        let mut names = HashMap::new();
+        names.insert(fin([0.0, 0.0]), "origin");
+        names.insert(fin([1.0, 0.0]), "x");
+        assert_eq!(names.get(&fin([-0.0, 0.0])), Some(&"origin")); //-0 and 0 are the same key
+        assert_eq!(names.get(&fin([1.0, 0.0])), Some(&"x"));
+
+        let tree: BTreeMap<_, _> = names.into_iter().collect();
+        assert_eq!(tree.keys().next(), Some(&fin([0.0, 0.0])));
+
+        let unique: HashSet<_> = [fin([1.0]), fin([1.0]), fin([2.0])].into_iter().collect();
+        assert_eq!(unique.len(), 2);
+    }
+}
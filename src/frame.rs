@@ -0,0 +1,287 @@
+use super::*;
+use num_traits::Float;
+use std::marker::PhantomData;
+
+// Coordinate frame tagged vectors
+// Framed<F, T, N> is an NVec<T, N> that remembers which reference frame F it's expressed in
+// F is any type you like, usually an empty struct, e.g. struct World; struct Body;
+// The ops only exist between vectors in the same frame, so adding a world frame vector to a body frame one won't compile
+// To move between frames you need a FrameTransform<From, To, T, N>, which makes every frame change explicit
+// The tag is a PhantomData so it costs nothing - a Framed is exactly the same size as the NVec inside it
+
+/// An NVec tagged with the reference frame it's expressed in.
+///
+/// Mixing frames is rejected at compile time:
+///
+/// ```compile_fail
+/// use nvecslib::*;
+/// struct World;
+/// struct Body;
+/// let a: Framed<World, f64, 3> = Framed::new([1.0, 2.0, 3.0]);
+/// let b: Framed<Body, f64, 3> = Framed::new([1.0, 2.0, 3.0]);
+/// let c = a + b;
+/// ```
+#[repr(transparent)]
+pub struct Framed<F, T: Numerical, const N: usize> {
+    pub vec: NVec<T, N>,
+    frame: PhantomData<fn() -> F>, // fn() -> F so the tag never affects Send/Sync or drop checking
+}
+
+impl<F, T: Numerical, const N: usize> Framed<F, T, N> {
+    pub fn new(components: [T; N]) -> Self {
+        Framed::from_nvec(NVec::new(components))
+    }
+
+    // tags a bare NVec as being in frame F
+    pub fn from_nvec(vec: NVec<T, N>) -> Self {
+        Framed { vec, frame: PhantomData }
+    }
+
+    // drops the tag - you're on your own from here
+    pub fn into_nvec(self) -> NVec<T, N> {
+        self.vec
+    }
+}
+
+// Implemented by hand since derive would demand F: Clone etc. even though F is never stored
+impl<F, T: Numerical, const N: usize> Copy for Framed<F, T, N> {}
+
+impl<F, T: Numerical, const N: usize> Clone for Framed<F, T, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<F, T: Numerical, const N: usize> PartialEq for Framed<F, T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.vec == other.vec
+    }
+}
+
+// only between vectors in the same frame, like ==
+impl<F, T: Numerical + ApproxEq, const N: usize> ApproxEq for Framed<F, T, N> {
+    type Epsilon = T::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        T::default_epsilon()
+    }
+
+    fn default_max_relative() -> Self::Epsilon {
+        T::default_max_relative()
+    }
+
+    fn default_max_ulps() -> u32 {
+        T::default_max_ulps()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.vec.abs_diff_eq(&other.vec, epsilon)
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: Self::Epsilon, max_relative: Self::Epsilon) -> bool {
+        self.vec.relative_eq(&other.vec, epsilon, max_relative)
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+        self.vec.ulps_eq(&other.vec, epsilon, max_ulps)
+    }
+}
+
+impl<F, T: Numerical + std::fmt::Debug, const N: usize> std::fmt::Debug for Framed<F, T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Framed<{}>({:?})", std::any::type_name::<F>(), self.vec.components)
+    }
+}
+
+
+
+// Operations //
+// all the same bounds as the NVec versions, just with both sides forced in to the same frame F
+
+impl<F, T, U, V, const N: usize> Add<Framed<F, U, N>> for Framed<F, T, N>
+where
+    T: Numerical + AsPrimitive<V> + Promote<U, Output = V>,
+    U: Numerical + AsPrimitive<V>,
+    V: Numerical + Add<Output = V> + 'static,
+{
+    type Output = Framed<F, V, N>;
+
+    fn add(self, rhs: Framed<F, U, N>) -> Self::Output {
+        Framed::from_nvec(self.vec + rhs.vec)
+    }
+}
+
+impl<F, T, U, V, const N: usize> Sub<Framed<F, U, N>> for Framed<F, T, N>
+where
+    T: Numerical + AsPrimitive<V> + Promote<U, Output = V>,
+    U: Numerical + AsPrimitive<V>,
+    V: Numerical + Sub<Output = V> + 'static,
+{
+    type Output = Framed<F, V, N>;
+
+    fn sub(self, rhs: Framed<F, U, N>) -> Self::Output {
+        Framed::from_nvec(self.vec - rhs.vec)
+    }
+}
+
+// dot product - a scalar, so the frame goes away
+impl<F, T, U, V, const N: usize> Mul<Framed<F, U, N>> for Framed<F, T, N>
+where
+    T: Numerical + AsPrimitive<V> + Promote<U, Output = V>,
+    U: Numerical + AsPrimitive<V>,
+    V: Numerical + Default + 'static,
+{
+    type Output = V;
+
+    fn mul(self, rhs: Framed<F, U, N>) -> Self::Output {
+        self.vec * rhs.vec
+    }
+}
+
+// scaling keeps the frame
+impl<F, T, U, V, const N: usize> Mul<U> for Framed<F, T, N>
+where
+    T: Numerical + AsPrimitive<V> + Promote<U, Output = V>,
+    U: Numerical + AsPrimitive<V>,
+    V: Numerical + 'static,
+{
+    type Output = Framed<F, V, N>;
+
+    fn mul(self, rhs: U) -> Self::Output {
+        Framed::from_nvec(self.vec * rhs)
+    }
+}
+
+impl<F, T, U, V> CrossProduct<Framed<F, U, 3>> for Framed<F, T, 3>
+where
+    T: Numerical + AsPrimitive<V> + Promote<U, Output = V>,
+    U: Numerical + AsPrimitive<V>,
+    V: Numerical + 'static,
+{
+    type Output = Framed<F, V, 3>;
+
+    fn cross(self, rhs: Framed<F, U, 3>) -> Self::Output {
+        Framed::from_nvec(self.vec.cross(rhs.vec))
+    }
+}
+
+impl<F, T, const N: usize> Magnitude for &Framed<F, T, N>
+where
+    T: Numerical,
+    f64: From<T>,
+{
+    type Output = f64;
+
+    fn magnitude(self) -> Self::Output {
+        self.vec.magnitude()
+    }
+
+    fn mag(self) -> Self::Output {
+        self.vec.mag()
+    }
+}
+
+
+
+// Frame transforms //
+
+// Takes things expressed in frame From and expresses them in frame To
+pub struct FrameTransform<From, To, T: Numerical, const N: usize> {
+    pub affine: Affine<T, N>,
+    frames: PhantomData<fn(From) -> To>,
+}
+
+impl<From, To, T: Float + Numerical, const N: usize> FrameTransform<From, To, T, N> {
+    // affine is the transform taking From coordinates to To coordinates
+    pub fn new(affine: Affine<T, N>) -> Self {
+        FrameTransform { affine, frames: PhantomData }
+    }
+
+    // positions get rotated and translated
+    pub fn transform_point(&self, p: Framed<From, T, N>) -> Framed<To, T, N> {
+        Framed::from_nvec(self.affine.transform_point(p.vec))
+    }
+
+    // directions only get rotated
+    pub fn transform_vector(&self, v: Framed<From, T, N>) -> Framed<To, T, N> {
+        Framed::from_nvec(self.affine.transform_vector(v.vec))
+    }
+
+    // the transform back the other way - None if it isn't invertible
+    pub fn inverse(&self) -> Option<FrameTransform<To, From, T, N>> {
+        Some(FrameTransform::new(self.affine.inverse()?))
+    }
+
+    // chains From -> To with To -> Next, so the frames have to line up
+    pub fn then<Next>(&self, next: &FrameTransform<To, Next, T, N>) -> FrameTransform<From, Next, T, N> {
+        FrameTransform::new(next.affine * self.affine)
+    }
+}
+
+impl<From, To, T: Numerical, const N: usize> Copy for FrameTransform<From, To, T, N> {}
+
+impl<From, To, T: Numerical, const N: usize> Clone for FrameTransform<From, To, T, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<From, To, T: Numerical + std::fmt::Debug, const N: usize> std::fmt::Debug for FrameTransform<From, To, T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "FrameTransform<{} -> {}>({:?})", std::any::type_name::<From>(), std::any::type_name::<To>(), self.affine)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_PI_2;
+
+    struct World;
+    struct Body;
+    struct Sensor;
+
+    #[test]
+    fn same_frame_test() {
+        println!("\n\nTesting for framed vector ops\n\n");
+        let a: Framed<World, i32, 3> = Framed::new([1, 2, 3]);
+        let b: Framed<World, f64, 3> = Framed::new([0.5, 0.5, 0.5]);
+
+        assert_eq!((a + b).into_nvec(), NVec::new([1.5, 2.5, 3.5]));
+        assert_eq!((a - b).into_nvec(), NVec::new([0.5, 1.5, 2.5]));
+        assert_eq!(a * b, 3.0);
+        assert_eq!((a * 2).into_nvec(), NVec::new([2, 4, 6]));
+        assert_eq!(a.cross(a).into_nvec(), NVec::new([0, 0, 0]));
+        assert_eq!(a.mag(), NVec::new([1, 2, 3]).mag());
+
+        // the tag is free
+        assert_eq!(std::mem::size_of::<Framed<World, f64, 3>>(), std::mem::size_of::<NVec<f64, 3>>());
+    }
+
+    #[test]
+    fn transform_test() {
+        println!("\n\nTesting for frame transforms\n\n");
+        // body sits at (10, 0, 0) in the world, turned 90 degrees about z
+        let body_to_world: FrameTransform<Body, World, f64, 3> = FrameTransform::new(
+            Affine::translation(NVec::new([10.0, 0.0, 0.0])) * Affine::from_axis_angle(NVec::new([0.0, 0.0, 1.0]), FRAC_PI_2),
+        );
+        let sensor_to_body: FrameTransform<Sensor, Body, f64, 3> = FrameTransform::new(Affine::translation(NVec::new([0.0, 0.0, 1.0])));
+
+        let p: Framed<Body, f64, 3> = Framed::new([1.0, 0.0, 0.0]);
+        assert_nvec_approx_eq!(body_to_world.transform_point(p), Framed::new([10.0, 1.0, 0.0]), epsilon = 1e-12);
+        assert_nvec_approx_eq!(body_to_world.transform_vector(p), Framed::new([0.0, 1.0, 0.0]), epsilon = 1e-12);
+
+        let world_to_body = body_to_world.inverse().unwrap();
+        assert_nvec_approx_eq!(world_to_body.transform_point(body_to_world.transform_point(p)), p, epsilon = 1e-12);
+
+        let sensor_to_world = sensor_to_body.then(&body_to_world);
+        let s: Framed<Sensor, f64, 3> = Framed::new([1.0, 0.0, 0.0]);
+        assert_nvec_approx_eq!(sensor_to_world.transform_point(s), Framed::new([10.0, 1.0, 1.0]), epsilon = 1e-12);
+
+        // only vectors in the same frame can be combined after the transform
+        let w: Framed<World, f64, 3> = Framed::new([1.0, 1.0, 1.0]);
+        assert_nvec_approx_eq!(sensor_to_world.transform_point(s) + w, Framed::new([11.0, 2.0, 2.0]), epsilon = 1e-12);
+    }
+}
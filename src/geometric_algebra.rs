@@ -0,0 +1,346 @@
+use super::*;
+use num_traits::Float;
+
+// Geometric algebra over an N dimensional Euclidean space
+// A multivector has one coefficient per basis blade - 2^N of them
+// Each blade is stored at the index whose bits say which basis vectors it's made of,
+// e.g. for N = 3, index 0b000 is the scalar, 0b001 is e1, 0b110 is e2^e3, 0b111 the pseudoscalar
+// The cross product is the 3D only special case of the outer product (via the dual), rotors do the same job as quaternions in any dimension
+// 2^N can't be written as an array length with stable const generics, so the blades live in a Vec
+// That's why it's private - every way in checks there are exactly 2^N of them, so the ops never have to
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Multivector<T, const N: usize> {
+    blades: Vec<T>,
+}
+
+// grade of a blade is how many basis vectors make it up
+fn grade_of(blade: usize) -> usize {
+    blade.count_ones() as usize
+}
+
+// sign picked up reordering e_a * e_b in to canonical order - counts how many swaps it takes
+// all basis vectors square to +1, so there's no metric to worry about
+fn reorder_sign(a: usize, b: usize) -> bool {
+    let mut a = a >> 1;
+    let mut swaps = 0;
+    while a != 0 {
+        swaps += (a & b).count_ones();
+        a >>= 1;
+    }
+    swaps % 2 == 1 //true if negative
+}
+
+impl<T: Float, const N: usize> Multivector<T, N> {
+    pub const BLADES: usize = 1 << N;
+
+    pub fn zero() -> Self {
+        Multivector { blades: vec![T::zero(); Self::BLADES] }
+    }
+
+    // every coefficient at once, in bitmask order - Err unless there are exactly 2^N
+    pub fn from_blades(blades: Vec<T>) -> Result<Self, NVecError> {
+        if blades.len() != Self::BLADES {
+            return Err(NVecError::DimensionMismatch { expected: Self::BLADES, found: blades.len() });
+        }
+        Ok(Multivector { blades })
+    }
+
+    // slices, so the coefficients can be changed but not added or removed
+    pub fn blades(&self) -> &[T] {
+        &self.blades
+    }
+
+    pub fn blades_mut(&mut self) -> &mut [T] {
+        &mut self.blades
+    }
+
+    pub fn scalar(s: T) -> Self {
+        let mut m = Self::zero();
+        m.blades[0] = s;
+        m
+    }
+
+    // a single basis blade with the given coefficient - blade is the bitmask of basis vectors
+    pub fn blade(blade: usize, value: T) -> Self {
+        assert!(blade < Self::BLADES, "Blade index out of range for this dimension");
+        let mut m = Self::zero();
+        m.blades[blade] = value;
+        m
+    }
+
+    // the basis vector e_i (0 indexed)
+    pub fn basis(i: usize) -> Self {
+        assert!(i < N, "Basis vector index out of range for this dimension");
+        Self::blade(1 << i, T::one())
+    }
+
+    // embeds an NVec as a grade 1 multivector
+    pub fn from_vector(v: NVec<T, N>) -> Self
+    where
+        T: Numerical,
+    {
+        let mut m = Self::zero();
+        for i in 0..N {
+            m.blades[1 << i] = v.components[i];
+        }
+        m
+    }
+
+    // reads back the grade 1 part as an NVec - everything else is dropped
+    pub fn to_vector(&self) -> NVec<T, N>
+    where
+        T: Numerical,
+    {
+        NVec::new(core::array::from_fn(|i| self.blades[1 << i]))
+    }
+
+    pub fn scalar_part(&self) -> T {
+        self.blades[0]
+    }
+
+    // general product - every blade with every blade
+    pub fn geometric(&self, rhs: &Self) -> Self {
+        self.product(rhs, |_, _| true)
+    }
+
+    // wedge - only the parts made of distinct basis vectors survive
+    pub fn outer(&self, rhs: &Self) -> Self {
+        self.product(rhs, |a, b| a & b == 0)
+    }
+
+    // left contraction - the part of rhs "left over" after removing self, grade(rhs) - grade(self)
+    pub fn inner(&self, rhs: &Self) -> Self {
+        self.product(rhs, |a, b| a & !b == 0)
+    }
+
+    // the scalar part of the geometric product
+    pub fn scalar_product(&self, rhs: &Self) -> T {
+        (0..Self::BLADES)
+            .map(|i| self.blades[i] * rhs.blades[i] * if reorder_sign(i, i) { -T::one() } else { T::one() })
+            .fold(T::zero(), |acc, elem| acc + elem)
+    }
+
+    // shared by all the products, keep decides which blade pairs contribute
+    fn product(&self, rhs: &Self, keep: impl Fn(usize, usize) -> bool) -> Self {
+        let mut result = Self::zero();
+
+        for a in 0..Self::BLADES {
+            if self.blades[a] == T::zero() {
+                continue;
+            }
+            for b in 0..Self::BLADES {
+                if rhs.blades[b] == T::zero() || !keep(a, b) {
+                    continue;
+                }
+                let term = self.blades[a] * rhs.blades[b];
+                // e_i e_i = 1, so the repeated vectors cancel leaving the xor
+                result.blades[a ^ b] = if reorder_sign(a, b) { result.blades[a ^ b] - term } else { result.blades[a ^ b] + term };
+            }
+        }
+
+        result
+    }
+
+    // just the blades of grade k
+    pub fn grade(&self, k: usize) -> Self {
+        let mut m = Self::zero();
+        for i in 0..Self::BLADES {
+            if grade_of(i) == k {
+                m.blades[i] = self.blades[i];
+            }
+        }
+        m
+    }
+
+    // reverses the order of the vectors in every blade - flips the sign of grades 2, 3, 6, 7....
+    pub fn reverse(&self) -> Self {
+        let mut m = self.clone();
+        for i in 0..Self::BLADES {
+            let k = grade_of(i);
+            if (k * k.saturating_sub(1) / 2) % 2 == 1 {
+                m.blades[i] = -m.blades[i];
+            }
+        }
+        m
+    }
+
+    pub fn scale(&self, s: T) -> Self {
+        Multivector { blades: self.blades.iter().map(|b| *b * s).collect() }
+    }
+
+    pub fn norm(&self) -> T {
+        self.scalar_product(&self.reverse()).abs().sqrt()
+    }
+
+    pub fn normalize(&self) -> Self {
+        let n = self.norm();
+        if n == T::zero() {
+            return self.clone();
+        }
+        self.scale(T::one() / n)
+    }
+
+    // rotor that rotates the direction of from on to the direction of to, in the plane they span
+    pub fn rotor(from: NVec<T, N>, to: NVec<T, N>) -> Self
+    where
+        T: Numerical,
+    {
+        let a = Self::from_vector(from).normalize();
+        let b = Self::from_vector(to).normalize();
+
+        // R = (1 + ba) / |1 + ba| is the half way rotor
+        let r = Self::scalar(T::one()) + b.geometric(&a);
+        if r.norm() > T::epsilon().sqrt() {
+            return r.normalize();
+        }
+
+        // opposite directions - rotate by pi through any plane containing a, found by Gram-Schmidt on the most perpendicular axis
+        let smallest = (0..N)
+            .min_by(|&i, &j| a.blades[1 << i].abs().partial_cmp(&a.blades[1 << j].abs()).unwrap_or(std::cmp::Ordering::Equal))
+            .unwrap_or(0);
+        let e = Self::basis(smallest);
+        let p = (e.clone() - a.scale(a.scalar_product(&e))).normalize();
+
+        p.geometric(&a)
+    }
+
+    // rotor that rotates by angle radians in the plane of a then b (a towards b)
+    pub fn rotor_from_plane(a: NVec<T, N>, b: NVec<T, N>, angle: T) -> Self
+    where
+        T: Numerical,
+    {
+        let plane = Self::from_vector(a).outer(&Self::from_vector(b)).normalize();
+        let half = angle / (T::one() + T::one());
+
+        // exp(-B angle / 2) = cos(angle / 2) - B sin(angle / 2)
+        Self::scalar(half.cos()) - plane.scale(half.sin())
+    }
+
+    // the sandwich product R v R~ - applies a rotor to a vector
+    pub fn rotate(&self, v: NVec<T, N>) -> NVec<T, N>
+    where
+        T: Numerical,
+    {
+        self.geometric(&Self::from_vector(v)).geometric(&self.reverse()).to_vector()
+    }
+}
+
+
+
+// Operations //
+
+// * is the geometric product
+impl<T: Float, const N: usize> Mul for Multivector<T, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        self.geometric(&rhs)
+    }
+}
+
+// ^ is the outer (wedge) product
+impl<T: Float, const N: usize> BitXor for Multivector<T, N> {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self {
+        self.outer(&rhs)
+    }
+}
+
+// | is the inner product (left contraction)
+impl<T: Float, const N: usize> BitOr for Multivector<T, N> {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.inner(&rhs)
+    }
+}
+
+impl<T: Float, const N: usize> Add for Multivector<T, N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Multivector { blades: self.blades.iter().zip(rhs.blades.iter()).map(|(a, b)| *a + *b).collect() }
+    }
+}
+
+impl<T: Float, const N: usize> Sub for Multivector<T, N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Multivector { blades: self.blades.iter().zip(rhs.blades.iter()).map(|(a, b)| *a - *b).collect() }
+    }
+}
+
+impl<T: Float, const N: usize> Neg for Multivector<T, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.scale(-T::one())
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn product_test() {
+        println!("\n\nTesting for multivector products\n\n");
+        type M3 = Multivector<f64, 3>;
+        let e1 = M3::basis(0);
+        let e2 = M3::basis(1);
+        let e3 = M3::basis(2);
+
+        assert_eq!(e1.clone() * e1.clone(), M3::scalar(1.0));
+        assert_eq!(M3::from_blades(vec![0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]), Ok(e1.clone()));
+        assert_eq!(M3::from_blades(vec![1.0; 4]), Err(NVecError::DimensionMismatch { expected: 8, found: 4 }));
+        let mut m = M3::zero();
+        m.blades_mut()[0b110] = 2.0;
+        assert_eq!(m.blades(), &[0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 2.0, 0.0]);
+        assert_eq!(e1.clone() * e2.clone(), M3::blade(0b011, 1.0));
+        assert_eq!(e2.clone() * e1.clone(), M3::blade(0b011, -1.0));
+        assert_eq!(e1.clone() ^ e1.clone(), M3::zero());
+        assert_eq!((e1.clone() ^ e2.clone()) ^ e3.clone(), M3::blade(0b111, 1.0));
+        assert_eq!(e1.clone() | (e1.clone() ^ e2.clone()), e2.clone());
+        assert_eq!(e2.clone() | e1.clone(), M3::zero());
+        assert_eq!(M3::blade(0b011, 1.0).reverse(), M3::blade(0b011, -1.0));
+        assert_eq!(M3::blade(0b111, 1.0).reverse(), M3::blade(0b111, -1.0));
+
+        // in 3D the wedge of two vectors is the dual of the cross product: a^b = (a x b) I
+        let a = NVec::new([1.0, 2.0, 3.0]);
+        let b = NVec::new([-2.0, 0.5, 4.0]);
+        let wedge = M3::from_vector(a) ^ M3::from_vector(b);
+        let dual = (wedge.clone() * M3::blade(0b111, -1.0)).grade(1).to_vector();
+        assert_nvec_approx_eq!(dual, a.cross(b), epsilon = 1e-12);
+
+        // ab = a.b + a^b
+        let ab = M3::from_vector(a) * M3::from_vector(b);
+        assert_eq!(ab.scalar_part(), a * b);
+        assert_eq!(ab.grade(2), wedge);
+    }
+
+    #[test]
+    fn rotor_test() {
+        println!("\n\nTesting for rotors\n\n");
+        let a = NVec::new([1.0, 0.0, 0.0, 0.0, 0.0]);
+        let b = NVec::new([0.0, 0.0, 0.0, 3.0, 4.0]);
+        let r = Multivector::rotor(a, b);
+        assert_nvec_approx_eq!(r.rotate(a), b * 0.2, epsilon = 1e-12);
+        assert!((r.rotate(NVec::new([0.0, 1.0, 0.0, 0.0, 0.0])).components[1] - 1.0).abs() < 1e-12); //out of plane is untouched
+
+        let flip = Multivector::rotor(a, a * -1.0);
+        assert_nvec_approx_eq!(flip.rotate(a), a * -1.0, epsilon = 1e-12);
+
+        // matches the quaternion for the same rotation in 3D
+        let axis_x = NVec::new([1.0, 0.0, 0.0]);
+        let axis_y = NVec::new([0.0, 1.0, 0.0]);
+        let r = Multivector::<f64, 3>::rotor_from_plane(axis_x, axis_y, 0.7);
+        let q = Quaternion::from_axis_angle(NVec::new([0.0, 0.0, 1.0]), 0.7);
+        let v = NVec::new([0.3, -1.2, 2.0]);
+        assert_nvec_approx_eq!(r.rotate(v), q.rotate(v), epsilon = 1e-12);
+    }
+}
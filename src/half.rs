@@ -0,0 +1,358 @@
+use super::*;
+
+// 16 bit floats - storage only types for halving the memory of big vector stores
+// There's no f16 hardware support we can rely on, so these just hold the bits and do all their maths in f32
+// Every op converts up to f32, does the work, then rounds back down (round to nearest, ties to even)
+// Operating with an f32 or f64 promotes the result to that type, so nothing is lost when mixing in full precision values
+
+// IEEE 754 binary16 - 1 sign bit, 5 exponent bits, 10 mantissa bits
+#[derive(Copy, Clone, Default)]
+pub struct F16(u16);
+
+// bfloat16 - the top half of an f32, so same range as f32 but only 7 mantissa bits
+#[derive(Copy, Clone, Default)]
+pub struct BF16(u16);
+
+impl F16 {
+    pub const ZERO: F16 = F16(0x0000);
+    pub const ONE: F16 = F16(0x3c00);
+    pub const INFINITY: F16 = F16(0x7c00);
+    pub const NEG_INFINITY: F16 = F16(0xfc00);
+    pub const NAN: F16 = F16(0x7e00);
+    pub const MAX: F16 = F16(0x7bff); // 65504
+
+    pub const fn from_bits(bits: u16) -> Self {
+        F16(bits)
+    }
+
+    pub const fn to_bits(self) -> u16 {
+        self.0
+    }
+
+    pub fn from_f32(x: f32) -> Self {
+        let bits = x.to_bits();
+        let sign = ((bits >> 16) & 0x8000) as u16;
+        let exp = ((bits >> 23) & 0xff) as i32;
+        let man = bits & 0x7f_ffff;
+
+        if exp == 0xff {
+            // infinity stays infinity, NaN stays a (quiet) NaN keeping what it can of the payload
+            return if man == 0 { F16(sign | 0x7c00) } else { F16(sign | 0x7e00 | (man >> 13) as u16) };
+        }
+
+        let e = exp - 127 + 15; //rebias the exponent
+
+        if e >= 0x1f {
+            return F16(sign | 0x7c00); // too big - overflows to infinity
+        }
+
+        if e <= 0 {
+            // lands in the subnormal range (or underflows to zero)
+            if e < -10 {
+                return F16(sign);
+            }
+            let m = man | 0x80_0000; //put back the implicit leading bit
+            let shift = (14 - e) as u32;
+            let half = 1 << (shift - 1);
+            let rem = m & ((1 << shift) - 1);
+            let mut r = m >> shift;
+            if rem > half || (rem == half && r & 1 == 1) {
+                r += 1;
+            }
+            return F16(sign | r as u16);
+        }
+
+        let mut r = ((e as u32) << 10) | (man >> 13);
+        let rem = man & 0x1fff;
+        if rem > 0x1000 || (rem == 0x1000 && r & 1 == 1) {
+            r += 1; // a carry here rolls in to the exponent, which is exactly right (even up to infinity)
+        }
+        F16(sign | r as u16)
+    }
+
+    pub fn to_f32(self) -> f32 {
+        let sign = ((self.0 & 0x8000) as u32) << 16;
+        let exp = ((self.0 >> 10) & 0x1f) as u32;
+        let man = (self.0 & 0x3ff) as u32;
+
+        if exp == 0 {
+            // zero or subnormal - man * 2^-24 is exact in f32
+            let v = man as f32 * f32::from_bits(0x3380_0000);
+            return if sign != 0 { -v } else { v };
+        }
+
+        if exp == 0x1f {
+            return f32::from_bits(sign | 0x7f80_0000 | (man << 13));
+        }
+
+        f32::from_bits(sign | ((exp + 112) << 23) | (man << 13))
+    }
+}
+
+impl BF16 {
+    pub const ZERO: BF16 = BF16(0x0000);
+    pub const ONE: BF16 = BF16(0x3f80);
+    pub const INFINITY: BF16 = BF16(0x7f80);
+    pub const NEG_INFINITY: BF16 = BF16(0xff80);
+    pub const NAN: BF16 = BF16(0x7fc0);
+    pub const MAX: BF16 = BF16(0x7f7f);
+
+    pub const fn from_bits(bits: u16) -> Self {
+        BF16(bits)
+    }
+
+    pub const fn to_bits(self) -> u16 {
+        self.0
+    }
+
+    pub fn from_f32(x: f32) -> Self {
+        let bits = x.to_bits();
+
+        if x.is_nan() {
+            return BF16(((bits >> 16) | 0x0040) as u16); //force it quiet so truncating the payload can't make it infinity
+        }
+
+        let round = 0x7fff + ((bits >> 16) & 1); //ties to even
+        BF16((bits.wrapping_add(round) >> 16) as u16)
+    }
+
+    pub fn to_f32(self) -> f32 {
+        f32::from_bits((self.0 as u32) << 16)
+    }
+}
+
+// Both types share everything else, just with different conversions
+macro_rules! half {
+    ($($type:ident), *) => {
+        $(
+            impl From<f32> for $type {
+                fn from(x: f32) -> Self {
+                    $type::from_f32(x)
+                }
+            }
+
+            impl From<$type> for f32 {
+                fn from(x: $type) -> Self {
+                    x.to_f32()
+                }
+            }
+
+            impl From<$type> for f64 {
+                fn from(x: $type) -> Self {
+                    x.to_f32() as f64 // needed for magnitude
+                }
+            }
+
+            impl std::fmt::Debug for $type {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    write!(f, "{:?}", self.to_f32())
+                }
+            }
+
+            impl std::fmt::Display for $type {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    std::fmt::Display::fmt(&self.to_f32(), f) //passes the precision etc through
+                }
+            }
+
+            // compare by value not by bits, so -0 == 0 and NaN != NaN like any other float
+            impl PartialEq for $type {
+                fn eq(&self, other: &Self) -> bool {
+                    self.to_f32() == other.to_f32()
+                }
+            }
+
+            impl PartialOrd for $type {
+                fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                    self.to_f32().partial_cmp(&other.to_f32())
+                }
+            }
+
+            impl Add for $type {
+                type Output = Self;
+
+                fn add(self, rhs: Self) -> Self {
+                    $type::from_f32(self.to_f32() + rhs.to_f32())
+                }
+            }
+
+            impl Sub for $type {
+                type Output = Self;
+
+                fn sub(self, rhs: Self) -> Self {
+                    $type::from_f32(self.to_f32() - rhs.to_f32())
+                }
+            }
+
+            impl Mul for $type {
+                type Output = Self;
+
+                fn mul(self, rhs: Self) -> Self {
+                    $type::from_f32(self.to_f32() * rhs.to_f32())
+                }
+            }
+
+            impl Div for $type {
+                type Output = Self;
+
+                fn div(self, rhs: Self) -> Self {
+                    $type::from_f32(self.to_f32() / rhs.to_f32())
+                }
+            }
+
+            impl Neg for $type {
+                type Output = Self;
+
+                fn neg(self) -> Self {
+                    $type(self.0 ^ 0x8000) //just flip the sign bit
+                }
+            }
+
+            impl Numerical for $type {}
+
+            impl AsPrimitive<$type> for $type {
+                fn as_(self) -> $type {
+                    self
+                }
+            }
+
+            impl AsPrimitive<f32> for $type {
+                fn as_(self) -> f32 {
+                    self.to_f32()
+                }
+            }
+
+            impl AsPrimitive<f64> for $type {
+                fn as_(self) -> f64 {
+                    self.to_f32() as f64
+                }
+            }
+
+            promote_self!($type, $type => $type);
+            promote!($type, f32 => f32);
+            promote!($type, f64 => f64);
+        )*
+    };
+}
+
+half!(F16, BF16);
+
+// F16 and BF16 can't hold each other exactly, so meet in the middle at f32
+promote!(F16, BF16 => f32);
+
+
+// Bulk conversions for whole vectors
+
+impl<const N: usize> NVec<f32, N> {
+    pub fn to_f16(self) -> NVec<F16, N> {
+        NVec::new(self.components.map(F16::from_f32))
+    }
+
+    pub fn to_bf16(self) -> NVec<BF16, N> {
+        NVec::new(self.components.map(BF16::from_f32))
+    }
+}
+
+impl<const N: usize> NVec<F16, N> {
+    pub fn to_f32(self) -> NVec<f32, N> {
+        NVec::new(self.components.map(F16::to_f32))
+    }
+}
+
+impl<const N: usize> NVec<BF16, N> {
+    pub fn to_f32(self) -> NVec<f32, N> {
+        NVec::new(self.components.map(BF16::to_f32))
+    }
+}
+
+impl<const N: usize> From<NVec<f32, N>> for NVec<F16, N> {
+    fn from(v: NVec<f32, N>) -> Self {
+        v.to_f16()
+    }
+}
+
+impl<const N: usize> From<NVec<f32, N>> for NVec<BF16, N> {
+    fn from(v: NVec<f32, N>) -> Self {
+        v.to_bf16()
+    }
+}
+
+impl<const N: usize> From<NVec<F16, N>> for NVec<f32, N> {
+    fn from(v: NVec<F16, N>) -> Self {
+        v.to_f32()
+    }
+}
+
+impl<const N: usize> From<NVec<BF16, N>> for NVec<f32, N> {
+    fn from(v: NVec<BF16, N>) -> Self {
+        v.to_f32()
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversion_test() {
+        println!("\n\nTesting for half precision conversions\n\n");
+
+        // exactly representable values survive the round trip
+        for x in [0.0f32, -0.0, 1.0, -2.5, 0.25, 65504.0, 2f32.powi(-14), 2f32.powi(-24)] {
+            assert_eq!(F16::from_f32(x).to_f32(), x);
+        }
+        for x in [0.0f32, 1.0, -2.5, 3.140625, 2f32.powi(100)] {
+            assert_eq!(BF16::from_f32(x).to_f32(), x);
+        }
+
+        // ties go to even - 1 + 2^-11 sits halfway between 1 and the next F16 up
+        assert_eq!(F16::from_f32(1.0 + 2f32.powi(-11)).to_bits(), 0x3c00);
+        assert_eq!(F16::from_f32(1.0 + 3.0 * 2f32.powi(-11)).to_bits(), 0x3c02);
+        assert_eq!(BF16::from_f32(1.0 + 2f32.powi(-8)).to_bits(), 0x3f80);
+        assert_eq!(BF16::from_f32(1.0 + 3.0 * 2f32.powi(-8)).to_bits(), 0x3f82);
+
+        // overflow, underflow and specials
+        assert_eq!(F16::from_f32(65520.0).to_bits(), F16::INFINITY.to_bits());
+        assert_eq!(F16::from_f32(1.0e-10).to_bits(), 0);
+        assert_eq!(F16::from_f32(2f32.powi(-25) * 1.5).to_bits(), 1); //just over halfway to the smallest subnormal
+        assert!(F16::from_f32(f32::NAN).to_f32().is_nan());
+        assert!(BF16::from_f32(f32::NAN).to_f32().is_nan());
+        assert_eq!(F16::from_f32(f32::NEG_INFINITY).to_f32(), f32::NEG_INFINITY);
+    }
+
+    #[test]
+    fn same_type_test() {
+        println!("\n\nTesting for half precision vector ops\n\n");
+        let a: NVec<F16, 3> = NVec::new([1.0f32, 2.0, 3.0]).to_f16();
+        let b: NVec<F16, 3> = NVec::new([0.5f32, -1.0, 4.0]).to_f16();
+
+        assert_eq!((a + b).to_f32(), NVec::new([1.5f32, 1.0, 7.0]));
+        assert_eq!((a - b).to_f32(), NVec::new([0.5f32, 3.0, -1.0]));
+        assert_eq!((a * b).to_f32(), 10.5);
+        assert_eq!(a.cross(b).to_f32(), NVec::new([11.0f32, -2.5, -2.0]));
+        assert_eq!(a.mag(), 14f64.sqrt());
+
+        let c: NVec<BF16, 3> = NVec::new([1.0f32, 2.0, 3.0]).into();
+        assert_eq!(NVec::<f32, 3>::from(c + c), NVec::new([2.0f32, 4.0, 6.0]));
+    }
+
+    #[test]
+    fn casting_test() {
+        println!("\n\nTesting for half precision promotion\n\n");
+        let a: NVec<F16, 3> = NVec::new([1.0f32, 2.0, 3.0]).to_f16();
+        let b: NVec<f32, 3> = NVec::new([0.1, 0.2, 0.3]);
+        let c: NVec<BF16, 3> = NVec::new([1.0f32, 1.0, 1.0]).to_bf16();
+
+        let d: NVec<f32, 3> = a + b;
+        assert_eq!(d, NVec::new([1.1f32, 2.2, 3.3]));
+        assert_eq!(b + a, d);
+
+        let e: NVec<f64, 3> = a * 2.0f64;
+        assert_eq!(e, NVec::new([2.0, 4.0, 6.0]));
+
+        let f: NVec<f32, 3> = a + c;
+        assert_eq!(f, NVec::new([2.0f32, 3.0, 4.0]));
+    }
+}
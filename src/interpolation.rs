@@ -0,0 +1,515 @@
+use super::*;
+use num_traits::{Float, NumCast};
+
+// Interpolation and curves through sequences of NVecs
+//     lerp                         straight line between two vectors
+//     nlerp / slerp                between two directions, staying unit length
+//     QuadraticBezier, CubicBezier single segment Bezier curves
+//     CatmullRom                   passes through every control point
+//     BSpline                      uniform cubic B-spline, smooth but doesn't touch the control points
+// Every curve implements Curve, which is parameterised over t in [0, 1] across the whole curve
+// Curve gives you derivatives, arc length (via ArcLength) and adaptive sampling in to polylines for free
+// Everything is done with the ordinary NVec add and scalar mul ops, so it works for any N
+
+// Shorthand for the bounds the NVec ops need when everything is the same float type
+pub trait CurveScalar: Float + Numerical + Default + AsPrimitive<Self> + Promote<Self, Output = Self> {}
+
+impl<T: Float + Numerical + Default + AsPrimitive<T> + Promote<T, Output = T>> CurveScalar for T {}
+
+
+
+// Two point interpolation //
+
+// a at t = 0, b at t = 1, and carries on in a straight line outside that
+pub fn lerp<T: CurveScalar, const N: usize>(a: NVec<T, N>, b: NVec<T, N>, t: T) -> NVec<T, N> {
+    a + (b - a) * t
+}
+
+// lerp then normalise - cheaper than slerp but doesn't move at a constant angular speed
+// None if the straight line passes through zero, i.e. a and b point in opposite directions and t is halfway
+pub fn nlerp<T: CurveScalar, const N: usize>(a: UnitVec<T, N>, b: UnitVec<T, N>, t: T) -> Option<UnitVec<T, N>> {
+    UnitVec::new(lerp(*a, *b, t))
+}
+
+// constant angular speed along the great circle between a and b
+pub fn slerp<T: CurveScalar, const N: usize>(a: UnitVec<T, N>, b: UnitVec<T, N>, t: T) -> UnitVec<T, N> {
+    a.slerp(&b, t)
+}
+
+
+
+// The Curve trait //
+
+pub trait Curve<T: CurveScalar, const N: usize> {
+    // the position at t, where t runs from 0 at the start to 1 at the end
+    fn point(&self, t: T) -> NVec<T, N>;
+
+    // d(point)/dt
+    fn derivative(&self, t: T) -> NVec<T, N>;
+
+    // n + 1 evenly spaced (in t) points including both ends
+    fn sample(&self, n: usize) -> Vec<NVec<T, N>> {
+        let n = n.max(1);
+        (0..=n).map(|i| self.point(from_usize::<T>(i) / from_usize::<T>(n))).collect()
+    }
+
+    // a polyline that stays within tolerance of the curve
+    // splits a span in half whenever its midpoint is further than tolerance from the chord, so flat bits get few points and tight bends get lots
+    // distance is to the chord as a line segment, so a straight curve that just moves unevenly in t isn't subdivided
+    fn sample_adaptive(&self, tolerance: T) -> Vec<NVec<T, N>> {
+        let start = self.point(T::zero());
+        let end = self.point(T::one());
+        let mut points = vec![start];
+        subdivide(self, T::zero(), start, T::one(), end, tolerance, 0, &mut points);
+        points
+    }
+
+    // approximate length from a polyline with the given number of segments
+    fn length(&self, segments: usize) -> T {
+        let points = self.sample(segments);
+        points.windows(2).fold(T::zero(), |acc, w| acc + length(w[1] - w[0]))
+    }
+}
+
+// the midpoint test can be fooled by curves that cross their chord halfway along (an S-bend), so always go a few levels in
+const MIN_DEPTH: usize = 3;
+const MAX_DEPTH: usize = 16;
+
+#[allow(clippy::too_many_arguments)]
+fn subdivide<T, C, const N: usize>(curve: &C, t0: T, p0: NVec<T, N>, t1: T, p1: NVec<T, N>, tolerance: T, depth: usize, points: &mut Vec<NVec<T, N>>)
+where
+    T: CurveScalar,
+    C: Curve<T, N> + ?Sized,
+{
+    let two = T::one() + T::one();
+    let tm = (t0 + t1) / two;
+    let pm = curve.point(tm);
+    let deviation = distance_to_segment(pm, p0, p1);
+
+    if depth >= MAX_DEPTH || (depth >= MIN_DEPTH && deviation <= tolerance) {
+        points.push(p1);
+        return;
+    }
+
+    subdivide(curve, t0, p0, tm, pm, tolerance, depth + 1, points);
+    subdivide(curve, tm, pm, t1, p1, tolerance, depth + 1, points);
+}
+
+
+
+// Arc length parameterisation //
+
+// Lets you move along a curve by distance rather than by t, e.g. for constant speed animation
+// Built from a table of (t, distance so far) pairs, and inverted by binary search plus linear interpolation
+pub struct ArcLength<'a, T: CurveScalar, C: Curve<T, N> + ?Sized, const N: usize> {
+    curve: &'a C,
+    ts: Vec<T>,
+    lengths: Vec<T>,
+}
+
+impl<'a, T: CurveScalar, C: Curve<T, N> + ?Sized, const N: usize> ArcLength<'a, T, C, N> {
+    // more segments gives a more accurate table
+    pub fn new(curve: &'a C, segments: usize) -> Self {
+        let segments = segments.max(1);
+        let ts: Vec<T> = (0..=segments).map(|i| from_usize::<T>(i) / from_usize::<T>(segments)).collect();
+
+        let mut lengths = Vec::with_capacity(ts.len());
+        let mut total = T::zero();
+        let mut prev = curve.point(T::zero());
+        for t in ts.iter() {
+            let p = curve.point(*t);
+            total = total + length(p - prev);
+            lengths.push(total);
+            prev = p;
+        }
+
+        ArcLength { curve, ts, lengths }
+    }
+
+    pub fn total_length(&self) -> T {
+        *self.lengths.last().unwrap()
+    }
+
+    // the t that's distance s along the curve, clamped to the ends
+    pub fn t_at_length(&self, s: T) -> T {
+        if s <= T::zero() {
+            return T::zero();
+        }
+        if s >= self.total_length() {
+            return T::one();
+        }
+
+        // first entry with length >= s, which is never the 0th since s > 0
+        let i = self.lengths.partition_point(|l| *l < s);
+        let (l0, l1) = (self.lengths[i - 1], self.lengths[i]);
+        let (t0, t1) = (self.ts[i - 1], self.ts[i]);
+        if l1 == l0 {
+            return t0;
+        }
+        t0 + (t1 - t0) * (s - l0) / (l1 - l0)
+    }
+
+    pub fn point_at_length(&self, s: T) -> NVec<T, N> {
+        self.curve.point(self.t_at_length(s))
+    }
+
+    // u in [0, 1] as a fraction of the total length
+    pub fn point_at_fraction(&self, u: T) -> NVec<T, N> {
+        self.point_at_length(u * self.total_length())
+    }
+
+    // n + 1 points evenly spaced along the curve by distance
+    pub fn sample_uniform(&self, n: usize) -> Vec<NVec<T, N>> {
+        let n = n.max(1);
+        (0..=n).map(|i| self.point_at_fraction(from_usize::<T>(i) / from_usize::<T>(n))).collect()
+    }
+}
+
+
+
+// Bezier curves //
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct QuadraticBezier<T, const N: usize> {
+    pub points: [NVec<T, N>; 3],
+}
+
+impl<T: CurveScalar, const N: usize> QuadraticBezier<T, N> {
+    pub fn new(p0: NVec<T, N>, p1: NVec<T, N>, p2: NVec<T, N>) -> Self {
+        QuadraticBezier { points: [p0, p1, p2] }
+    }
+
+    // constant for a quadratic: 2 (p0 - 2 p1 + p2)
+    pub fn second_derivative(&self) -> NVec<T, N> {
+        let two = T::one() + T::one();
+        let [p0, p1, p2] = self.points;
+        (p0 - p1 * two + p2) * two
+    }
+}
+
+impl<T: CurveScalar, const N: usize> Curve<T, N> for QuadraticBezier<T, N> {
+    // (1-t)^2 p0 + 2(1-t)t p1 + t^2 p2
+    fn point(&self, t: T) -> NVec<T, N> {
+        let two = T::one() + T::one();
+        let s = T::one() - t;
+        combine(&self.points, &[s * s, two * s * t, t * t])
+    }
+
+    // 2(1-t)(p1 - p0) + 2t(p2 - p1)
+    fn derivative(&self, t: T) -> NVec<T, N> {
+        let two = T::one() + T::one();
+        let [p0, p1, p2] = self.points;
+        (p1 - p0) * (two * (T::one() - t)) + (p2 - p1) * (two * t)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CubicBezier<T, const N: usize> {
+    pub points: [NVec<T, N>; 4],
+}
+
+impl<T: CurveScalar, const N: usize> CubicBezier<T, N> {
+    pub fn new(p0: NVec<T, N>, p1: NVec<T, N>, p2: NVec<T, N>, p3: NVec<T, N>) -> Self {
+        CubicBezier { points: [p0, p1, p2, p3] }
+    }
+
+    // 6(1-t)(p2 - 2 p1 + p0) + 6t(p3 - 2 p2 + p1)
+    pub fn second_derivative(&self, t: T) -> NVec<T, N> {
+        let two = T::one() + T::one();
+        let six = two * (two + T::one());
+        let [p0, p1, p2, p3] = self.points;
+        (p2 - p1 * two + p0) * (six * (T::one() - t)) + (p3 - p2 * two + p1) * (six * t)
+    }
+}
+
+impl<T: CurveScalar, const N: usize> Curve<T, N> for CubicBezier<T, N> {
+    // (1-t)^3 p0 + 3(1-t)^2 t p1 + 3(1-t)t^2 p2 + t^3 p3
+    fn point(&self, t: T) -> NVec<T, N> {
+        let three = T::one() + T::one() + T::one();
+        let s = T::one() - t;
+        combine(&self.points, &[s * s * s, three * s * s * t, three * s * t * t, t * t * t])
+    }
+
+    // 3(1-t)^2 (p1 - p0) + 6(1-t)t (p2 - p1) + 3t^2 (p3 - p2)
+    fn derivative(&self, t: T) -> NVec<T, N> {
+        let three = T::one() + T::one() + T::one();
+        let s = T::one() - t;
+        let [p0, p1, p2, p3] = self.points;
+        combine(&[p1 - p0, p2 - p1, p3 - p2], &[three * s * s, (three + three) * s * t, three * t * t])
+    }
+}
+
+
+
+// Splines over a slice of control points //
+
+// Uniform Catmull-Rom spline - goes through every control point, with the tangent at each one parallel to the line between its neighbours
+// The end points are repeated so the curve reaches them too, giving points.len() - 1 segments
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatmullRom<T, const N: usize> {
+    pub points: Vec<NVec<T, N>>,
+}
+
+impl<T: CurveScalar, const N: usize> CatmullRom<T, N> {
+    // needs at least 2 points
+    pub fn new(points: &[NVec<T, N>]) -> Self {
+        assert!(points.len() >= 2, "A Catmull-Rom spline needs at least 2 points");
+        CatmullRom { points: points.to_vec() }
+    }
+
+    // the four points around segment i, repeating the ends
+    fn segment(&self, i: usize) -> [NVec<T, N>; 4] {
+        let last = self.points.len() - 1;
+        [self.points[i.saturating_sub(1)], self.points[i], self.points[i + 1], self.points[(i + 2).min(last)]]
+    }
+}
+
+impl<T: CurveScalar, const N: usize> Curve<T, N> for CatmullRom<T, N> {
+    // 0.5 (2 p1 + (p2 - p0) u + (2 p0 - 5 p1 + 4 p2 - p3) u^2 + (3 p1 - p0 - 3 p2 + p3) u^3)
+    fn point(&self, t: T) -> NVec<T, N> {
+        let (i, u) = locate(t, self.points.len() - 1);
+        let one = T::one();
+        let (two, three) = (one + one, one + one + one);
+        let half = one / two;
+        let (u2, u3) = (u * u, u * u * u);
+
+        let weights = [
+            half * (-u + two * u2 - u3),
+            half * (two - (two + three) * u2 + three * u3),
+            half * (u + (two + two) * u2 - three * u3),
+            half * (u3 - u2),
+        ];
+        combine(&self.segment(i), &weights)
+    }
+
+    fn derivative(&self, t: T) -> NVec<T, N> {
+        let segments = self.points.len() - 1;
+        let (i, u) = locate(t, segments);
+        let one = T::one();
+        let (two, three) = (one + one, one + one + one);
+        let half = one / two;
+        let u2 = u * u;
+
+        let weights = [
+            half * (-one + (two + two) * u - three * u2),
+            half * (-(two * (two + three)) * u + three * three * u2),
+            half * (one + (two * (two + two)) * u - three * three * u2),
+            half * (three * u2 - two * u),
+        ];
+        // chain rule since u moves `segments` times faster than t
+        combine(&self.segment(i), &weights) * from_usize::<T>(segments)
+    }
+}
+
+// Uniform cubic B-spline - C2 smooth, but only approximates the control points
+// n control points give n - 3 segments
+#[derive(Debug, Clone, PartialEq)]
+pub struct BSpline<T, const N: usize> {
+    pub points: Vec<NVec<T, N>>,
+}
+
+impl<T: CurveScalar, const N: usize> BSpline<T, N> {
+    // needs at least 4 points
+    pub fn new(points: &[NVec<T, N>]) -> Self {
+        assert!(points.len() >= 4, "A cubic B-spline needs at least 4 points");
+        BSpline { points: points.to_vec() }
+    }
+}
+
+impl<T: CurveScalar, const N: usize> Curve<T, N> for BSpline<T, N> {
+    // 1/6 ((1-u)^3 p0 + (3u^3 - 6u^2 + 4) p1 + (-3u^3 + 3u^2 + 3u + 1) p2 + u^3 p3)
+    fn point(&self, t: T) -> NVec<T, N> {
+        let (i, u) = locate(t, self.points.len() - 3);
+        let one = T::one();
+        let three = one + one + one;
+        let (four, six) = (three + one, three + three);
+        let s = one - u;
+        let (u2, u3) = (u * u, u * u * u);
+
+        let weights = [
+            s * s * s / six,
+            (three * u3 - six * u2 + four) / six,
+            (-three * u3 + three * u2 + three * u + one) / six,
+            u3 / six,
+        ];
+        combine(&self.points[i..i + 4], &weights)
+    }
+
+    fn derivative(&self, t: T) -> NVec<T, N> {
+        let segments = self.points.len() - 3;
+        let (i, u) = locate(t, segments);
+        let one = T::one();
+        let two = one + one;
+        let three = two + one;
+        let s = one - u;
+        let u2 = u * u;
+
+        let weights = [
+            -s * s / two,
+            (three * u2 - (two + two) * u) / two,
+            (-three * u2 + two * u + one) / two,
+            u2 / two,
+        ];
+        combine(&self.points[i..i + 4], &weights) * from_usize::<T>(segments)
+    }
+}
+
+
+
+// Helpers //
+
+// sum of w_i p_i
+fn combine<T: CurveScalar, const N: usize>(points: &[NVec<T, N>], weights: &[T]) -> NVec<T, N> {
+    points
+        .iter()
+        .zip(weights.iter())
+        .fold(NVec::new([T::zero(); N]), |acc, (p, w)| acc + *p * *w)
+}
+
+// which of `segments` equal segments t lands in, and how far along it
+fn locate<T: CurveScalar>(t: T, segments: usize) -> (usize, T) {
+    let scaled = t.max(T::zero()).min(T::one()) * from_usize::<T>(segments);
+    let i = scaled.floor().to_usize().unwrap_or(0).min(segments - 1);
+    (i, scaled - from_usize::<T>(i))
+}
+
+// distance from p to the closest point on the segment a-b
+fn distance_to_segment<T: CurveScalar, const N: usize>(p: NVec<T, N>, a: NVec<T, N>, b: NVec<T, N>) -> T {
+    let ab = b - a;
+    let ap = p - a;
+    let ab2 = ab * ab;
+    if ab2 == T::zero() {
+        return length(ap);
+    }
+    let along = ((ap * ab) / ab2).max(T::zero()).min(T::one());
+    length(ap - ab * along)
+}
+
+fn from_usize<T: CurveScalar>(n: usize) -> T {
+    <T as NumCast>::from(n).unwrap()
+}
+
+// in T rather than the f64 that magnitude gives, so f32 curves stay f32
+fn length<T: CurveScalar, const N: usize>(v: NVec<T, N>) -> T {
+    (v * v).sqrt()
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // central difference to check the analytic derivatives against
+    fn numeric_derivative<C: Curve<f64, N>, const N: usize>(curve: &C, t: f64) -> NVec<f64, N> {
+        let h = 1e-6;
+        (curve.point(t + h) - curve.point(t - h)) * (0.5 / h)
+    }
+
+    #[test]
+    fn lerp_test() {
+        println!("\n\nTesting for lerp, nlerp and slerp\n\n");
+        let a = NVec::new([0.0, 0.0, 0.0]);
+        let b = NVec::new([2.0, 4.0, -6.0]);
+        assert_eq!(lerp(a, b, 0.5), NVec::new([1.0, 2.0, -3.0]));
+        assert_eq!(lerp(a, b, 1.5), NVec::new([3.0, 6.0, -9.0]));
+
+        let x = UnitVec::<f64, 2>::basis(0);
+        let y = UnitVec::<f64, 2>::basis(1);
+        let s = 0.5f64.sqrt();
+        assert_nvec_approx_eq!(*nlerp(x, y, 0.5).unwrap(), NVec::new([s, s]), epsilon = 1e-12);
+        assert_nvec_approx_eq!(*slerp(x, y, 0.5), NVec::new([s, s]), epsilon = 1e-12);
+        assert_eq!(nlerp(x, -x, 0.5), None);
+
+        // slerp sweeps equal angles, nlerp doesn't
+        let third = slerp(x, y, 1.0 / 3.0);
+        assert!((x.angle_to(&third) - std::f64::consts::FRAC_PI_6).abs() < 1e-12);
+        assert!((x.angle_to(&nlerp(x, y, 1.0 / 3.0).unwrap()) - std::f64::consts::FRAC_PI_6).abs() > 1e-3);
+    }
+
+    #[test]
+    fn bezier_test() {
+        println!("\n\nTesting for Bezier curves\n\n");
+        let q = QuadraticBezier::new(NVec::new([0.0, 0.0]), NVec::new([1.0, 2.0]), NVec::new([2.0, 0.0]));
+        assert_eq!(q.point(0.0), NVec::new([0.0, 0.0]));
+        assert_eq!(q.point(0.5), NVec::new([1.0, 1.0]));
+        assert_eq!(q.point(1.0), NVec::new([2.0, 0.0]));
+        assert_nvec_approx_eq!(q.derivative(0.3), numeric_derivative(&q, 0.3), epsilon = 1e-6);
+        assert_eq!(q.second_derivative(), NVec::new([0.0, -8.0]));
+
+        let c = CubicBezier::new(
+            NVec::new([0.0, 0.0, 0.0]),
+            NVec::new([1.0, 3.0, 0.0]),
+            NVec::new([3.0, 3.0, 1.0]),
+            NVec::new([4.0, 0.0, 1.0]),
+        );
+        assert_eq!(c.point(0.0), c.points[0]);
+        assert_eq!(c.point(1.0), c.points[3]);
+        assert_eq!(c.derivative(0.0), (c.points[1] - c.points[0]) * 3.0);
+        assert_nvec_approx_eq!(c.derivative(0.7), numeric_derivative(&c, 0.7), epsilon = 1e-6);
+
+        let h = 1e-5;
+        let numeric_second = (c.derivative(0.4 + h) - c.derivative(0.4 - h)) * (0.5 / h);
+        assert_nvec_approx_eq!(c.second_derivative(0.4), numeric_second, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn spline_test() {
+        println!("\n\nTesting for Catmull-Rom and B-spline curves\n\n");
+        let points = [NVec::new([0.0, 0.0]), NVec::new([1.0, 1.0]), NVec::new([2.0, 0.0]), NVec::new([3.0, 1.0]), NVec::new([4.0, 0.0])];
+
+        // Catmull-Rom goes through every point, one segment per gap
+        let cr = CatmullRom::new(&points);
+        for (i, p) in points.iter().enumerate() {
+            assert_nvec_approx_eq!(cr.point(i as f64 / 4.0), *p, epsilon = 1e-12);
+        }
+        for t in [0.1, 0.37, 0.5, 0.9] {
+            assert_nvec_approx_eq!(cr.derivative(t), numeric_derivative(&cr, t), epsilon = 1e-6);
+        }
+
+        // B-spline of collinear evenly spaced points is just the line between the middle ones
+        let line: Vec<NVec<f64, 3>> = (0..6).map(|i| NVec::new([i as f64, 2.0 * i as f64, 0.0])).collect();
+        let bs = BSpline::new(&line);
+        assert_nvec_approx_eq!(bs.point(0.0), line[1], epsilon = 1e-12);
+        assert_nvec_approx_eq!(bs.point(1.0), line[4], epsilon = 1e-12);
+        assert_nvec_approx_eq!(bs.point(0.5), NVec::new([2.5, 5.0, 0.0]), epsilon = 1e-12);
+
+        let wiggly = BSpline::new(&points);
+        for t in [0.2, 0.5, 0.8] {
+            assert_nvec_approx_eq!(wiggly.derivative(t), numeric_derivative(&wiggly, t), epsilon = 1e-6);
+        }
+        // continuous across the join between segments
+        assert_nvec_approx_eq!(wiggly.point(0.5 - 1e-9), wiggly.point(0.5 + 1e-9), epsilon = 1e-6);
+    }
+
+    #[test]
+    fn arc_length_and_sampling_test() {
+        println!("\n\nTesting for arc length and adaptive sampling\n\n");
+        // a quarter circle approximated by a cubic - length should be close to pi/2
+        let k = 0.5522847498;
+        let arc = CubicBezier::new(NVec::new([1.0, 0.0]), NVec::new([1.0, k]), NVec::new([k, 1.0]), NVec::new([0.0, 1.0]));
+        assert!((arc.length(1000) - std::f64::consts::FRAC_PI_2).abs() < 1e-3);
+
+        // a straight line with bunched up control points moves unevenly in t but evenly by length
+        let line = CubicBezier::new(NVec::new([0.0, 0.0]), NVec::new([0.1, 0.0]), NVec::new([0.2, 0.0]), NVec::new([3.0, 0.0]));
+        let table = ArcLength::new(&line, 2000);
+        assert!((table.total_length() - 3.0).abs() < 1e-9);
+        assert!((line.point(0.5).components[0] - 1.5).abs() > 0.1);
+        for (i, p) in table.sample_uniform(6).iter().enumerate() {
+            assert!((p.components[0] - 0.5 * i as f64).abs() < 1e-4);
+        }
+
+        // tighter tolerance means more points, and every span stays within it
+        let coarse = arc.sample_adaptive(1e-2);
+        let fine = arc.sample_adaptive(1e-5);
+        assert!(fine.len() > coarse.len());
+        assert_eq!(fine[0], arc.point(0.0));
+        assert_eq!(*fine.last().unwrap(), arc.point(1.0));
+        for p in fine.iter() {
+            assert!((Magnitude::mag(p) - 1.0).abs() < 1e-3); //still on the circle-ish
+        }
+
+        // a straight line doesn't need more than the minimum subdivision
+        assert_eq!(line.sample_adaptive(1e-6).len(), (1 << MIN_DEPTH) + 1);
+    }
+}
@@ -0,0 +1,325 @@
+use super::*;
+
+// Interval arithmetic - each component is a closed range [lo, hi] that is guaranteed to contain the true value
+// Rust gives us no control over the FPU rounding mode, so instead we round to nearest as normal and then
+// nudge the lower bound down and the upper bound up by one ulp (outward rounding)
+// Every basic float op (+ - * / sqrt) is correctly rounded, so the true result is always within one ulp - the nudge covers it
+// This makes NVec<Interval<f64>, N> give enclosures for add, sub, dot, cross and magnitude with no changes to those impls
+
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Interval<T> {
+    pub lo: T,
+    pub hi: T,
+}
+
+impl<T: Copy + PartialOrd> Interval<T> {
+    // panics if the bounds are the wrong way round (or NaN) since that isn't an interval at all
+    pub fn new(lo: T, hi: T) -> Self {
+        assert!(lo <= hi, "Interval lower bound must not be greater than upper bound");
+        Interval { lo, hi }
+    }
+
+    // a degenerate interval containing exactly one value
+    pub fn point(x: T) -> Self {
+        Interval { lo: x, hi: x }
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        self.lo <= x && x <= self.hi
+    }
+}
+
+// Written lo..hi rather than [lo, hi] so an interval is one token inside an NVec, e.g. [0.5..0.75, -1..1]
+// which is what FromStr reads back. Any precision applies to both bounds, rounded to nearest like any other float
+impl<T: std::fmt::Display> std::fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.lo, f)?;
+        write!(f, "..")?;
+        std::fmt::Display::fmt(&self.hi, f)
+    }
+}
+
+// Comparison between intervals is only certain when they don't overlap
+// Identical intervals compare equal, overlapping ones can't be ordered so give None
+impl<T: PartialOrd> PartialOrd for Interval<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        if self.lo == other.lo && self.hi == other.hi {
+            Some(std::cmp::Ordering::Equal)
+        } else if self.hi < other.lo {
+            Some(std::cmp::Ordering::Less)
+        } else if self.lo > other.hi {
+            Some(std::cmp::Ordering::Greater)
+        } else {
+            None
+        }
+    }
+}
+
+// Same problem as the rest of the crate - no way to say "for T in {f32, f64}", so a macro it is
+macro_rules! interval {
+    ($($type:ty), *) => {
+        $(
+            impl Interval<$type> {
+                // the whole real line - what you get when dividing by something that might be zero
+                pub fn entire() -> Self {
+                    Interval { lo: <$type>::NEG_INFINITY, hi: <$type>::INFINITY }
+                }
+
+                // rounded up so the width is never understated
+                pub fn width(&self) -> $type {
+                    if self.lo == self.hi {
+                        return 0.0;
+                    }
+                    (self.hi - self.lo).next_up()
+                }
+
+                pub fn midpoint(&self) -> $type {
+                    self.lo / 2.0 + self.hi / 2.0 //halve first so we can't overflow
+                }
+
+                // x * x - tighter than self * self, which can't know both sides are the same value and so may go negative
+                pub fn square(self) -> Self {
+                    let lo2 = self.lo * self.lo;
+                    let hi2 = self.hi * self.hi;
+
+                    if self.contains(0.0) {
+                        Interval { lo: 0.0, hi: lo2.max(hi2).next_up() }
+                    } else {
+                        Interval { lo: lo2.min(hi2).next_down().max(0.0), hi: lo2.max(hi2).next_up() }
+                    }
+                }
+
+                // the empty interval, both bounds NaN - what you get from an op with no real result at all
+                // nothing is contained in it and it compares unequal to everything, like NaN itself
+                pub fn empty() -> Self {
+                    Interval { lo: <$type>::NAN, hi: <$type>::NAN }
+                }
+
+                pub fn is_empty(&self) -> bool {
+                    self.lo.is_nan() || self.hi.is_nan()
+                }
+
+                // negative parts of the interval are outside the domain so are clipped off
+                // an interval that's entirely negative has no square root at all, so gives the empty interval
+                pub fn sqrt(self) -> Self {
+                    if self.hi < 0.0 || self.is_empty() {
+                        return Self::empty();
+                    }
+                    let lo = self.lo.max(0.0).sqrt();
+                    Interval { lo: if lo == 0.0 { 0.0 } else { lo.next_down() }, hi: self.hi.sqrt().next_up() }
+                }
+
+                // builds the tightest outwardly rounded interval around a set of candidate endpoints
+                // 0 * inf gives NaN, but as a bound it should be 0, so those are dropped
+                // (an empty operand would give NaNs too, so mul/div check for that before getting here)
+                fn hull(candidates: [$type; 4]) -> Self {
+                    let mut lo = <$type>::INFINITY;
+                    let mut hi = <$type>::NEG_INFINITY;
+
+                    for c in candidates {
+                        let c = if c.is_nan() { 0.0 } else { c };
+                        lo = lo.min(c);
+                        hi = hi.max(c);
+                    }
+
+                    Interval { lo: lo.next_down(), hi: hi.next_up() }
+                }
+            }
+
+            impl From<$type> for Interval<$type> {
+                fn from(x: $type) -> Self {
+                    Self::point(x)
+                }
+            }
+
+            impl Add for Interval<$type> {
+                type Output = Self;
+
+                fn add(self, rhs: Self) -> Self {
+                    Interval { lo: (self.lo + rhs.lo).next_down(), hi: (self.hi + rhs.hi).next_up() }
+                }
+            }
+
+            impl Sub for Interval<$type> {
+                type Output = Self;
+
+                fn sub(self, rhs: Self) -> Self {
+                    Interval { lo: (self.lo - rhs.hi).next_down(), hi: (self.hi - rhs.lo).next_up() }
+                }
+            }
+
+            impl Mul for Interval<$type> {
+                type Output = Self;
+
+                fn mul(self, rhs: Self) -> Self {
+                    if self.is_empty() || rhs.is_empty() {
+                        return Self::empty();
+                    }
+                    Self::hull([self.lo * rhs.lo, self.lo * rhs.hi, self.hi * rhs.lo, self.hi * rhs.hi])
+                }
+            }
+
+            impl Div for Interval<$type> {
+                type Output = Self;
+
+                fn div(self, rhs: Self) -> Self {
+                    if self.is_empty() || rhs.is_empty() {
+                        return Self::empty();
+                    }
+                    if rhs.contains(0.0) {
+                        return Self::entire();
+                    }
+                    Self::hull([self.lo / rhs.lo, self.lo / rhs.hi, self.hi / rhs.lo, self.hi / rhs.hi])
+                }
+            }
+
+            impl Neg for Interval<$type> {
+                type Output = Self;
+
+                fn neg(self) -> Self {
+                    Interval { lo: -self.hi, hi: -self.lo } //exact, no rounding needed
+                }
+            }
+
+            impl Numerical for Interval<$type> {}
+
+            // conversions needed so the existing ops can .as_() their way in to an interval
+            impl AsPrimitive<Interval<$type>> for Interval<$type> {
+                fn as_(self) -> Interval<$type> {
+                    self
+                }
+            }
+
+            impl AsPrimitive<Interval<$type>> for $type {
+                fn as_(self) -> Interval<$type> {
+                    Interval::point(self)
+                }
+            }
+
+            promote_self!(Interval<$type>, Interval<$type> => Interval<$type>);
+            promote!($type, Interval<$type> => Interval<$type>);
+
+            // The magnitude of an interval vector is itself an interval - the standard impl would squash it to an f64
+            impl<const N: usize> Magnitude for &NVec<Interval<$type>, N> {
+                type Output = Interval<$type>;
+
+                fn magnitude(self) -> Self::Output {
+                    let mut acc = Interval::point(0.0);
+
+                    for i in 0..N {
+                        acc = acc + self.components[i].square();
+                    };
+
+                    acc.sqrt()
+                }
+
+                fn mag(self) -> Self::Output {
+                    self.magnitude()
+                }
+            }
+        )*
+    };
+}
+
+interval!(f32, f64);
+
+// Mixing precisions - widen the f32 side, which is exact
+impl AsPrimitive<Interval<f64>> for Interval<f32> {
+    fn as_(self) -> Interval<f64> {
+        Interval { lo: self.lo as f64, hi: self.hi as f64 }
+    }
+}
+
+impl AsPrimitive<Interval<f64>> for f32 {
+    fn as_(self) -> Interval<f64> {
+        Interval::point(self as f64)
+    }
+}
+
+promote!(Interval<f32>, Interval<f64> => Interval<f64>);
+promote!(f32, Interval<f64> => Interval<f64>);
+promote!(f64, Interval<f32> => Interval<f64>);
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encloses<const N: usize>(a: NVec<Interval<f64>, N>, b: NVec<f64, N>) -> bool {
+        (0..N).all(|i| a.components[i].contains(b.components[i]))
+    }
+
+    #[test]
+    fn same_type_test() {
+        println!("\n\nTesting for interval vector ops\n\n");
+        let x = [0.1, -2.65, 3.4];
+        let y = [1.01, 0.2, -7.3];
+
+        let a: NVec<Interval<f64>, 3> = NVec::new(x.map(Interval::point));
+        let b: NVec<Interval<f64>, 3> = NVec::new(y.map(Interval::point));
+
+        let fa = NVec::new(x);
+        let fb = NVec::new(y);
+
+        assert!(encloses(a + b, fa + fb));
+        assert!(encloses(a - b, fa - fb));
+        assert!(encloses(a.cross(b), fa.cross(fb)));
+        assert!((a * b).contains(fa * fb));
+        assert!(a.mag().contains(fa.mag()));
+
+        // 0.1 + 0.2 isn't 0.3 in floats, but the enclosure must still hold the real answer
+        let s = Interval::point(0.1f64) + Interval::point(0.2f64);
+        assert!(s.contains(0.1 + 0.2));
+        assert!(s.width() > 0.0);
+    }
+
+    #[test]
+    fn casting_test() {
+        println!("\n\nTesting for interval promotion\n\n");
+        let a: NVec<Interval<f64>, 3> = NVec::new([Interval::new(1.0, 2.0), Interval::new(-1.0, 1.0), Interval::point(0.5)]);
+        let b: NVec<f64, 3> = NVec::new([4.0, -5.0, 200.0]);
+
+        let c = a + b;
+        assert!(c.components[0].contains(5.0) && c.components[0].contains(6.0));
+        assert!(c.components[1].contains(-6.0) && c.components[1].contains(-4.0));
+        assert!(c.components[2].contains(200.5));
+        assert_eq!(b + a, c);
+
+        let d = a * 2.0;
+        assert!(d.components[0].contains(2.0) && d.components[0].contains(4.0));
+
+        let e: NVec<f32, 3> = NVec::new([1.5, 2.5, 3.5]);
+        let f: NVec<Interval<f64>, 3> = a + e;
+        assert!(f.components[2].contains(4.0));
+    }
+
+    #[test]
+    fn interval_ops_test() {
+        let a = Interval::new(-1.0f64, 2.0f64);
+
+        assert!(a.contains(0.0) && !a.contains(2.5));
+        assert!(a.width() >= 3.0);
+        assert_eq!(a.square().lo, 0.0);
+        assert!((Interval::point(1.0f64) / a).contains(f64::INFINITY));
+        assert!(Interval::new(-4.0f64, 1.0f64).sqrt().contains(0.0));
+        assert!(!Interval::new(-4.0f64, 1.0f64).sqrt().is_empty());
+        assert!(Interval::new(0.0f32, 0.0f32).sqrt().contains(0.0));
+
+        let none = Interval::new(-4.0f64, -1.0f64).sqrt();
+        assert!(none.is_empty() && none.lo.is_nan() && none.hi.is_nan());
+        assert!(!none.contains(0.0) && !none.contains(f64::NAN));
+        assert!(Interval::<f64>::empty().sqrt().is_empty());
+
+        // empty stays empty through everything, it doesn't turn back in to an interval around 0
+        let empty = Interval::<f64>::empty();
+        assert!((empty * Interval::point(2.0)).is_empty());
+        assert!((Interval::point(1.0) / empty).is_empty());
+        assert!((empty / a).is_empty()); //a contains 0, but there's nothing to divide
+        assert!((Interval::point(0.0) * none).is_empty());
+        assert!((empty + Interval::point(1.0)).is_empty() && (Interval::point(1.0) - empty).is_empty());
+        assert!((Interval::new(0.0, 1.0) * Interval::<f64>::entire()).contains(0.0)); //0 * inf still counts as 0 though
+        assert!(Interval::point(1.0) < Interval::point(2.0));
+        assert_eq!(a.partial_cmp(&Interval::point(0.0)), None);
+    }
+}
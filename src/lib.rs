@@ -14,9 +14,12 @@ use std::ops::*;
 
 // This is a vector (fixed length array, mathematical vector) with N components
 // It implements many useful methods used in vector maths, e.g. vector addition, subtraction, and the dot product
+// There's no bound on T here - the ops all ask for Numerical themselves
+// This lets other component types that can't be Numerical (e.g. units, where m * m isn't m) still live in an NVec with their own ops
 
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub struct NVec<T: Numerical, const N: usize> {
+// Eq, Hash and Ord only exist when the components have them, i.e. integers but not floats
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NVec<T, const N: usize> {
     pub components: [T; N],
 }
 pub trait IsNvec{} //type signifier to help with different implementations of operation orders, e.g. 1 * a, a * 1
@@ -44,7 +47,7 @@ numerical!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, f32, f64);
 
 
 pub trait NewNVec<T: Numerical, const N: usize> {
-    fn new(components: [T; N]) -> NVec<T, N>;
+    fn new(components: [T; N]) -> Self;
 }
 
 //constructor function
@@ -54,11 +57,6 @@ impl<T: Numerical, const N: usize> NewNVec<T, N> for NVec<T, N> {
     }
 }
 
-//impl<T: Numerical, const N: usize> std::fmt::Display for NVec<T, N> {
-//    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-//        write!(f, "{:?}", self.components)
-//    }
-//} 
 
 
 
@@ -85,16 +83,91 @@ pub trait CrossProduct<Rhs = Self>{
     fn cross(self, rhs: Rhs) -> Self::Output; 
 }
 
+// Component by component utilities, so you don't have to reach in to .components for the everyday stuff
+// Each method only asks for what it needs, e.g. floor needs floats and abs needs a signed type
+pub trait ComponentWise<T, const N: usize>: Sized {
+    fn abs(self) -> Self where T: num_traits::Signed;
+    fn signum(self) -> Self where T: num_traits::Signed;
+    fn floor(self) -> Self where T: num_traits::Float;
+    fn ceil(self) -> Self where T: num_traits::Float;
+    fn round(self) -> Self where T: num_traits::Float;
+
+    // the smaller/larger of each pair of components, promoted like the other ops between two NVecs
+    fn min<U, V>(self, rhs: NVec<U, N>) -> NVec<V, N>
+    where
+        T: AsPrimitive<V> + Promote<U, Output = V>,
+        U: Numerical + AsPrimitive<V>,
+        V: Numerical + 'static;
+    fn max<U, V>(self, rhs: NVec<U, N>) -> NVec<V, N>
+    where
+        T: AsPrimitive<V> + Promote<U, Output = V>,
+        U: Numerical + AsPrimitive<V>,
+        V: Numerical + 'static;
+    fn clamp(self, lo: Self, hi: Self) -> Self;
+    fn clamp_scalar(self, lo: T, hi: T) -> Self; //same bounds for every component
+
+    // horizontal ops - squash the components down to one value
+    fn sum(self) -> T where T: num_traits::Zero;
+    fn product(self) -> T where T: num_traits::One;
+    fn min_element(self) -> T; //these panic for N = 0 since there's nothing to pick
+    fn max_element(self) -> T;
+    fn argmin(self) -> usize;
+    fn argmax(self) -> usize;
+
+    // apply a closure to every component (or pair of components), which can change the type
+    fn map<U, F: FnMut(T) -> U>(self, f: F) -> NVec<U, N>;
+    fn zip_with<U, V, F: FnMut(T, U) -> V>(self, rhs: NVec<U, N>, f: F) -> NVec<V, N>;
+}
+
 
 // Implementations
 
 // split up implementations for better readability
+pub mod error; //NVecError, and the checked component arithmetic behind the try_ ops
+pub use error::{NVecError, CheckedNumerical};
 pub mod add;
 pub mod sub;
 pub mod element_multiply;
 pub mod mul;
 pub mod cross;
 pub mod mag;
+pub mod component_wise;
+pub mod macros; //nvec!
+pub mod approx_eq; //ApproxEq and the assert_nvec_approx_eq! macros
+pub use approx_eq::ApproxEq;
+pub mod cast; //explicit component type conversions
+pub use cast::CastError;
+pub mod swizzle; //x(), xy(), zyx(), sub-vectors etc.
+pub mod const_ops; //const fn constructors and integer ops for compile time tables
+pub mod std_impls; //Index, iterators, conversions, Display etc.
+pub mod parse; //FromStr, reading back what Display writes
+pub mod csv; //CSV/TSV files of vectors
+pub use csv::{Columns, CsvError, CsvOptions, CsvReader, CsvWriter};
+mod checksum; //CRC-32 for the file formats
+pub mod npy; //NumPy .npy/.npz files
+pub use npy::{Endian, NpyError, NpyOptions};
+pub use std_impls::DisplayOptions;
+
+// Types built on top of NVec
+pub mod quaternion;
+pub use quaternion::Quaternion;
+pub mod dual_quaternion;
+pub use dual_quaternion::DualQuaternion;
+pub mod geometric_algebra;
+pub use geometric_algebra::Multivector;
+pub mod affine;
+pub use affine::Affine;
+pub mod point;
+pub use point::Point;
+pub mod frame;
+pub use frame::{Framed, FrameTransform};
+pub mod units;
+pub mod unit_vec;
+pub use unit_vec::UnitVec;
+pub mod coordinates;
+pub use coordinates::{Angle, Cylindrical, Hyperspherical, Polar, Spherical, SphericalConvention};
+pub mod interpolation;
+pub use interpolation::{lerp, Curve};
 
 pub mod test_macros; //the tests should be in each implementation file, but they use a common set of macros linked here
 
@@ -107,6 +180,7 @@ pub mod test_macros; //the tests should be in each implementation file, but they
 // However this isn't actually desireable although it may seem like it on the face - it would lead to cyclical references and impossible implementations for ops e.g. addition
 // An NVec is its own type, not just a wrapper, because it implements special operations - it needs to be used as such
 // The aim is that you should never have to deal directly with the component array because the type itself should implement everything you need
+// Index, AsRef<[T]> and IntoIterator (in std_impls.rs) give the read/write access you'd want from Deref without those problems
 
 //impl<T, const N: usize> Deref for NVec<T, N> {
 //    type Target = [T; N];
@@ -271,4 +345,20 @@ promote!(u64, f64 => f64);
 //promote!(u128, f64 => f64);
 
 // Float + Float => Larger Float
-promote!(f32, f64 => f64);
\ No newline at end of file
+promote!(f32, f64 => f64);
+
+
+
+// Additional component types //
+// These come after the promote! tower since they need the macros to build their own promotion rules
+
+pub mod interval;
+pub use interval::Interval;
+pub mod half;
+pub use half::{F16, BF16};
+pub mod finite;
+pub use finite::Finite;
+
+// Binary storage, after the component types since it covers all of them
+pub mod binary;
+pub use binary::{BinaryError, NVecReader, NVecWriter};
@@ -0,0 +1,185 @@
+use super::*;
+
+// The nvec! macro and the plumbing behind its concatenation form
+//     nvec![1.0, 2.0, 3.0]        list of components
+//     nvec![0.0; 4]               every component the same
+//     nvec![1, 2, 3 => f32]       components cast to the given type
+//     nvec![..a, 1.0, ..b]        vectors spread in (with ..) between scalars
+// Stable Rust can't add const generics together, so a concatenation can't work its own length out
+// It has to come from the context instead (a type annotation, or whatever it's used with) and is checked against the parts at compile time
+
+/// Builds an NVec.
+///
+/// ```
+/// use nvecslib::*;
+/// let a = nvec![1.0, 2.0];
+/// let b: NVec<f64, 5> = nvec![..a, 0.5, ..a];
+/// assert_eq!(b, NVec::new([1.0, 2.0, 0.5, 1.0, 2.0]));
+/// assert_eq!(nvec![7u8; 3], NVec::new([7, 7, 7]));
+/// assert_eq!(nvec![1, 2 => f64], NVec::new([1.0, 2.0]));
+/// ```
+///
+/// The wrong number of components is a compile error:
+///
+/// ```compile_fail
+/// use nvecslib::*;
+/// let v: NVec<f64, 3> = nvec![1.0, 2.0];
+/// ```
+///
+/// including when concatenating:
+///
+/// ```compile_fail
+/// use nvecslib::*;
+/// let a = nvec![1.0, 2.0];
+/// let v: NVec<f64, 4> = nvec![..a, 0.5, ..a];
+/// ```
+///
+/// Concatenation differs from the `nvec![a, 1.0, b]` form you might expect in two ways:
+///
+/// * Vectors need a leading `..`. The macro only sees expressions, so it can't tell
+///   a vector variable from a scalar one without being told.
+/// * The length isn't worked out from the parts. Stable Rust can't add const generics
+///   together, so the length has to come from a type annotation or from how the result
+///   is used. It's still checked against the parts at compile time. With nothing to go
+///   on it won't compile:
+///
+/// ```compile_fail
+/// use nvecslib::*;
+/// let a = nvec![1.0, 2.0];
+/// let v = nvec![..a, 0.5];
+/// ```
+#[macro_export]
+macro_rules! nvec {
+    // walk the list one item at a time, keeping both the plain scalars and every part
+    // [spread] gets set once a ..vector turns up, which switches to concatenating the parts
+    (@munch [$($s:expr),*] [$($p:expr),*] [$($mode:ident)?] .. $v:expr $(, $($rest:tt)*)?) => {
+        $crate::nvec!(@munch [$($s),*] [$($p,)* $v] [spread] $($($rest)*)?)
+    };
+    (@munch [$($s:expr),*] [$($p:expr),*] [$($mode:ident)?] $x:expr $(, $($rest:tt)*)?) => {
+        $crate::nvec!(@munch [$($s,)* $x] [$($p,)* $x] [$($mode)?] $($($rest)*)?)
+    };
+    (@munch [$($s:expr),*] [$($p:expr),*] []) => {
+        $crate::NVec { components: [$($s),*] }
+    };
+    (@munch [$($s:expr),*] [$($p:expr),*] [spread]) => {
+        $crate::macros::Concat::concat(($($p,)*))
+    };
+
+    () => {
+        $crate::NVec { components: [] }
+    };
+    ($x:expr; $n:expr) => {
+        $crate::NVec { components: [$x; $n] }
+    };
+    ($($x:expr),+ => $t:ty) => {
+        $crate::NVec { components: [$($x as $t),+] }
+    };
+    ($($tt:tt)+) => {
+        $crate::nvec!(@munch [] [] [] $($tt)+)
+    };
+}
+
+// Something that can go in a concatenation - a single component or a whole NVec
+// Only public so the macro can see it
+#[doc(hidden)]
+pub trait Part<T> {
+    const LEN: usize;
+    fn push_to(self, out: &mut Vec<T>);
+}
+
+// Numerical is local, so the compiler knows NVec never hits this one
+impl<T: Numerical> Part<T> for T {
+    const LEN: usize = 1;
+
+    fn push_to(self, out: &mut Vec<T>) {
+        out.push(self);
+    }
+}
+
+impl<T, const N: usize> Part<T> for NVec<T, N> {
+    const LEN: usize = N;
+
+    fn push_to(self, out: &mut Vec<T>) {
+        out.extend(self.components);
+    }
+}
+
+// A tuple of parts joined end to end in to an NVec<T, M>
+#[doc(hidden)]
+pub trait Concat<T> {
+    fn concat<const M: usize>(self) -> NVec<T, M>;
+}
+
+macro_rules! concat_tuple {
+    ($(($($part:ident),+)), *) => {
+        $(
+            #[allow(non_snake_case)]
+            impl<T, $($part: Part<T>),+> Concat<T> for ($($part,)+) {
+                fn concat<const M: usize>(self) -> NVec<T, M> {
+                    const { assert!(0 $(+ $part::LEN)+ == M, "nvec! parts don't add up to the length of the NVec") };
+                    let mut out = Vec::with_capacity(M);
+                    let ($($part,)+) = self;
+                    $($part.push_to(&mut out);)+
+                    NVec::try_from(out).unwrap_or_else(|_| unreachable!()) //the length was checked above
+                }
+            }
+        )*
+    };
+}
+
+concat_tuple!(
+    (A),
+    (A, B),
+    (A, B, C),
+    (A, B, C, D),
+    (A, B, C, D, E),
+    (A, B, C, D, E, F),
+    (A, B, C, D, E, F, G),
+    (A, B, C, D, E, F, G, H),
+    (A, B, C, D, E, F, G, H, I),
+    (A, B, C, D, E, F, G, H, I, J),
+    (A, B, C, D, E, F, G, H, I, J, K),
+    (A, B, C, D, E, F, G, H, I, J, K, L)
+);
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_test() {
+        println!("\n\nTesting for the nvec! macro\n\n");
+        assert_eq!(nvec![1, 2, 3], NVec::new([1, 2, 3]));
+        assert_eq!(nvec![1.5, -2.0,], NVec::new([1.5, -2.0])); //trailing comma
+        assert_eq!(nvec![0u16; 4], NVec::new([0, 0, 0, 0]));
+        assert_eq!(nvec![1, 2, 3 => f32], NVec::new([1.0f32, 2.0, 3.0]));
+        assert_eq!(nvec![-1.7, 2.9 => i32], NVec::new([-1, 2])); //an `as` cast, so it truncates
+
+        let x = 2.0;
+        assert_eq!(nvec![x * 2.0, (x - 1.0), -x], NVec::new([4.0, 1.0, -2.0]));
+        let empty: NVec<i32, 0> = nvec![];
+        assert_eq!(empty.components.len(), 0);
+    }
+
+    #[test]
+    fn concat_test() {
+        println!("\n\nTesting for nvec! concatenation\n\n");
+        let a = nvec![1.0, 2.0];
+        let b = nvec![3.0, 4.0, 5.0];
+
+        let joined: NVec<f64, 6> = nvec![..a, 0.0, ..b];
+        assert_eq!(joined, NVec::new([1.0, 2.0, 0.0, 3.0, 4.0, 5.0]));
+
+        let front: NVec<f64, 3> = nvec![9.0, ..a];
+        assert_eq!(front, NVec::new([9.0, 1.0, 2.0]));
+
+        // the length can come from how it's used rather than an annotation
+        assert_eq!(nvec![..a, ..a] + NVec::new([1.0; 4]), NVec::new([2.0, 3.0, 2.0, 3.0]));
+
+        // homogeneous coordinates in one go
+        let p: NVec<i32, 4> = nvec![..nvec![4, 5, 6], 1];
+        assert_eq!(p, NVec::new([4, 5, 6, 1]));
+    }
+}
@@ -14,7 +14,7 @@ where
         let mut acc: f64 = 0.0; //don't bother with integers, no point since going to root
 
         for i in 0..N { //N - 1?
-            acc = acc + (f64::from(self.components[i]) * f64::from(self.components[i])); //we could use square here but due to pow for ints and powi or powf for floats, simpler just to do this
+            acc += f64::from(self.components[i]) * f64::from(self.components[i]); //we could use square here but due to pow for ints and powi or powf for floats, simpler just to do this
         };
 
         acc.sqrt()
@@ -25,19 +25,69 @@ where
         let mut acc: f64 = 0.0; //don't bother with integers, no point since going to root
 
         for i in 0..N { //N - 1?
-            acc = acc + (f64::from(self.components[i]) * f64::from(self.components[i])); //we could use square here but due to pow for ints and powi or powf for floats, simpler just to do this
+            acc += f64::from(self.components[i]) * f64::from(self.components[i]); //we could use square here but due to pow for ints and powi or powf for floats, simpler just to do this
         };
 
         acc.sqrt()
     }
 }
 
+impl<T, const N: usize> NVec<T, N>
+where
+    T: Numerical,
+    f64: From<T>
+{
+    // The magnitude, but an Err if a component is inf/NaN or the answer is too big for an f64
+    // Scales by the biggest component first so the squares can't overflow when the result itself would fit
+    pub fn try_magnitude(&self) -> Result<f64, NVecError> {
+        let mut scale: f64 = 0.0;
+        let mut biggest = 0;
+        for (i, c) in self.components.iter().enumerate() {
+            let c = f64::from(*c);
+            if !c.is_finite() {
+                return Err(NVecError::NonFinite { index: i });
+            }
+            if c.abs() > scale {
+                scale = c.abs();
+                biggest = i;
+            }
+        }
+
+        if scale == 0.0 {
+            return Ok(0.0);
+        }
+
+        let acc: f64 = self.components.iter().map(|c| (f64::from(*c) / scale) * (f64::from(*c) / scale)).sum();
+        let result = scale * acc.sqrt();
+        if result.is_finite() {
+            Ok(result)
+        } else {
+            Err(NVecError::NonFinite { index: biggest })
+        }
+    }
+}
+
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn try_magnitude_test() {
+        println!("\n\nTesting for checked magnitude\n\n");
+        assert_eq!(NVec::new([3.0, 4.0]).try_magnitude(), Ok(5.0));
+        assert_eq!(NVec::new([0i32, 0, 0]).try_magnitude(), Ok(0.0));
+
+        // the plain version overflows squaring these, the checked one doesn't
+        let big = NVec::new([3e200, 4e200]);
+        assert_eq!(big.mag(), f64::INFINITY);
+        assert!((big.try_magnitude().unwrap() - 5e200).abs() < 1e188);
+
+        assert_eq!(NVec::new([1.0, f64::NAN]).try_magnitude(), Err(NVecError::NonFinite { index: 1 }));
+        assert_eq!(NVec::new([f64::MAX, f64::MAX]).try_magnitude(), Err(NVecError::NonFinite { index: 0 }));
+    }
+
     #[test]
     fn same_type_test() {
     
@@ -52,7 +102,7 @@ mod tests {
     fn casting_test(){
         println!("\n\nTesting for magnitude\n\n");
         let a: NVec<f64, 3> = NVec {
-            components: [1.01 as f64, -2.65 as f64, 3.4 as f64],
+            components: [1.01_f64, -2.65_f64, 3.4_f64],
         };
         let b: NVec<i32, 3> = NVec {
             components: [4, -5, 3],
@@ -36,7 +36,7 @@ where
 
     fn mul(self, rhs: U) -> Self::Output {
         let result: [V; N] =
-        core::array::from_fn(|i: usize| self.components[i].as_() * rhs.clone().as_());
+        core::array::from_fn(|i: usize| self.components[i].as_() * rhs.as_());
 
         NVec::new(result)
     }
@@ -73,6 +73,33 @@ macro_rules! right_handed_scalar_mul {
 
 right_handed_scalar_mul!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, f32, f64);
 
+// Checked versions of the above, an Err instead of a wrapped integer or an inf/NaN float
+// For the dot product the index is the term of the sum where it went wrong
+impl<T: Numerical, const N: usize> NVec<T, N> {
+    pub fn try_dot<U, V>(self, rhs: NVec<U, N>) -> Result<V, NVecError>
+    where
+        T: AsPrimitive<V> + Promote<U, Output = V>,
+        U: Numerical + AsPrimitive<V>,
+        V: CheckedNumerical + Default + 'static,
+    {
+        let mut acc = V::default();
+        for i in 0..N {
+            let term = self.components[i].as_().try_mul(rhs.components[i].as_(), i)?;
+            acc = acc.try_add(term, i)?;
+        }
+        Ok(acc)
+    }
+
+    pub fn try_scale<U, V>(self, rhs: U) -> Result<NVec<V, N>, NVecError>
+    where
+        T: AsPrimitive<V> + Promote<U, Output = V>,
+        U: Numerical + AsPrimitive<V>,
+        V: CheckedNumerical + 'static,
+    {
+        error::try_components(|i| self.components[i].as_().try_mul(rhs.as_(), i))
+    }
+}
+
 
 
 
@@ -115,6 +142,19 @@ right_handed_scalar_mul!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, f32,
 mod tests {
     use super::*;
 
+    #[test]
+    fn checked_test() {
+        println!("\n\nTesting for checked dot product and scaling\n\n");
+        assert_eq!(NVec::new([1i8, 2, 3]).try_dot(NVec::new([4i8, 5, 6])), Ok(32i8));
+        assert_eq!(NVec::new([10i8, 10]).try_dot(NVec::new([10i8, 3])), Err::<i8, _>(NVecError::Overflow { index: 1 })); //100 + 30
+        assert_eq!(NVec::new([100i8, 0]).try_dot(NVec::new([2i8, 0])), Err::<i8, _>(NVecError::Overflow { index: 0 })); //100 * 2
+        assert_eq!(NVec::new([1e200, 1.0]).try_dot(NVec::new([1e200, 1.0])), Err::<f64, _>(NVecError::NonFinite { index: 0 }));
+
+        assert_eq!(NVec::new([1u32, 2]).try_scale(3u32), Ok(NVec::new([3u32, 6])));
+        assert_eq!(NVec::new([1u32, u32::MAX]).try_scale(2u32), Err::<NVec<u32, 2>, _>(NVecError::Overflow { index: 1 }));
+        assert_eq!(NVec::new([0.5f32, f32::MAX]).try_scale(4.0f32), Err::<NVec<f32, 2>, _>(NVecError::NonFinite { index: 1 }));
+    }
+
     #[test]
     fn same_type_test() {
         //declare all the operations, then call the tests to be assembled below
@@ -129,7 +169,7 @@ mod tests {
 
         println!("\n\nTesting for scalar * NVec\n\n");
         let a: NVec<f64, 3> = NVec {
-            components: [1.01 as f64, -2.65 as f64, 3.4 as f64],
+            components: [1.01_f64, -2.65_f64, 3.4_f64],
         };
         let b: NVec<f64, 3> = NVec {
             components: [-1.01f64, 2.65f64, -3.4f64],
@@ -146,7 +186,7 @@ mod tests {
     fn casting_test(){
         println!("\n\nTesting for dot product\n\n");
         let a: NVec<f64, 3> = NVec {
-            components: [1.01 as f64, -2.65 as f64, 3.4 as f64],
+            components: [1.01_f64, -2.65_f64, 3.4_f64],
         };
         let b: NVec<i32, 3> = NVec {
             components: [4, -5, 200],
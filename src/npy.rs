@@ -0,0 +1,652 @@
+use super::*;
+use checksum::crc32;
+use std::fmt;
+use std::io::{self, Read, Write};
+
+// NumPy .npy files, and the .npz archives np.savez makes out of them
+// A 2D array with shape (M, N) is M vectors of N components, i.e. a Vec<NVec<T, N>>
+// Reads format versions 1, 2 and 3, either byte order, and C (row major) or Fortran (column major) layout
+// Writes version 1 (or 2 if the header somehow doesn't fit) in whichever byte order and layout you ask for
+// .npz archives are only read when the entries are stored, not compressed - np.savez yes, np.savez_compressed no
+//
+// numpy has no 128 bit integers, so i128/u128 get written as '<i16'/'<u16' which this crate reads back but numpy won't
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Endian {
+    #[default]
+    Little,
+    Big,
+}
+
+impl Endian {
+    #[cfg(target_endian = "little")]
+    pub const NATIVE: Endian = Endian::Little;
+    #[cfg(target_endian = "big")]
+    pub const NATIVE: Endian = Endian::Big;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct NpyOptions {
+    pub endian: Endian,
+    pub fortran_order: bool,
+}
+
+#[derive(Debug)]
+pub enum NpyError {
+    Io(io::Error),
+    // not a .npy file, or one this can't make sense of
+    Header(String),
+    // the file holds a different element type than asked for, both as numpy dtype strings e.g. '<f8'
+    Dtype { expected: String, found: String },
+    // the file isn't an (M, N) array for the N asked for
+    Shape { expected: usize, found: Vec<usize> },
+    // a broken or compressed .npz
+    Archive(String),
+    MissingArray(String),
+}
+
+impl fmt::Display for NpyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NpyError::Io(e) => write!(f, "{}", e),
+            NpyError::Header(message) => write!(f, "invalid .npy header: {}", message),
+            NpyError::Dtype { expected, found } => write!(f, "dtype mismatch: expected '{}' but the array is '{}'", expected, found),
+            NpyError::Shape { expected, found } => {
+                write!(f, "shape mismatch: expected (M, {}) but the array is {:?}", expected, found)
+            }
+            NpyError::Archive(message) => write!(f, "invalid .npz archive: {}", message),
+            NpyError::MissingArray(name) => write!(f, "no array named `{}` in the archive", name),
+        }
+    }
+}
+
+impl std::error::Error for NpyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            NpyError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for NpyError {
+    fn from(e: io::Error) -> Self {
+        NpyError::Io(e)
+    }
+}
+
+
+
+// Element types //
+
+// A component type with a numpy dtype - all the numerical! primitives
+pub trait NpyElement: Numerical {
+    const KIND: char; //'i', 'u' or 'f' as in the dtype string
+    const SIZE: usize;
+
+    fn read_bytes(bytes: &[u8], endian: Endian) -> Self;
+    fn write_bytes(self, endian: Endian, out: &mut Vec<u8>);
+
+    // e.g. '<f8', or '|u1' where byte order doesn't apply
+    fn descr(endian: Endian) -> String {
+        let order = match endian {
+            _ if Self::SIZE == 1 => '|',
+            Endian::Little => '<',
+            Endian::Big => '>',
+        };
+        format!("{}{}{}", order, Self::KIND, Self::SIZE)
+    }
+}
+
+macro_rules! npy_element {
+    ($(($type:ty, $kind:literal)), *) => {
+        $(
+            impl NpyElement for $type {
+                const KIND: char = $kind;
+                const SIZE: usize = std::mem::size_of::<$type>();
+
+                fn read_bytes(bytes: &[u8], endian: Endian) -> Self {
+                    let bytes = bytes.try_into().unwrap_or_else(|_| unreachable!()); //always handed SIZE bytes
+                    match endian {
+                        Endian::Little => <$type>::from_le_bytes(bytes),
+                        Endian::Big => <$type>::from_be_bytes(bytes),
+                    }
+                }
+
+                fn write_bytes(self, endian: Endian, out: &mut Vec<u8>) {
+                    match endian {
+                        Endian::Little => out.extend_from_slice(&self.to_le_bytes()),
+                        Endian::Big => out.extend_from_slice(&self.to_be_bytes()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+npy_element!(
+    (i8, 'i'), (i16, 'i'), (i32, 'i'), (i64, 'i'), (i128, 'i'),
+    (u8, 'u'), (u16, 'u'), (u32, 'u'), (u64, 'u'), (u128, 'u'),
+    (f32, 'f'), (f64, 'f')
+);
+
+
+
+// Reading //
+
+pub fn read_npy<R: Read, T: NpyElement, const N: usize>(mut reader: R) -> Result<Vec<NVec<T, N>>, NpyError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    parse_npy(&bytes)
+}
+
+// The header dict, e.g. {'descr': '<f8', 'fortran_order': False, 'shape': (3, 2), }
+struct Header {
+    descr: String,
+    fortran_order: bool,
+    shape: Vec<usize>,
+}
+
+fn parse_npy<T: NpyElement, const N: usize>(bytes: &[u8]) -> Result<Vec<NVec<T, N>>, NpyError> {
+    let header_error = |message: &str| NpyError::Header(message.to_string());
+
+    if !bytes.starts_with(MAGIC) || bytes.len() < 10 {
+        return Err(header_error("missing the \\x93NUMPY magic string"));
+    }
+    let (header_len, header_start) = match bytes[6] {
+        1 => (u16::from_le_bytes([bytes[8], bytes[9]]) as usize, 10),
+        2 | 3 if bytes.len() >= 12 => (u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]) as usize, 12),
+        major => return Err(NpyError::Header(format!("unsupported format version {}.{}", major, bytes[7]))),
+    };
+    let data_start = header_start + header_len;
+    let header = bytes.get(header_start..data_start).ok_or_else(|| header_error("file ends inside the header"))?;
+    let header = std::str::from_utf8(header).map_err(|_| header_error("header isn't text"))?;
+    let header = parse_header(header)?;
+
+    // dtype - byte order then kind then size, e.g. '<f8'
+    let mut descr = header.descr.chars();
+    let endian = match descr.clone().next() {
+        Some('<') | Some('|') => Endian::Little, //'|' is for single bytes, where it doesn't matter
+        Some('>') => Endian::Big,
+        Some('=') => Endian::NATIVE,
+        _ => return Err(NpyError::Header(format!("dtype '{}' has no byte order", header.descr))),
+    };
+    descr.next();
+    let kind = descr.next();
+    let size: Option<usize> = descr.as_str().parse().ok();
+    if kind != Some(T::KIND) || size != Some(T::SIZE) {
+        return Err(NpyError::Dtype { expected: T::descr(endian), found: header.descr });
+    }
+
+    let rows = match header.shape[..] {
+        [rows, n] if n == N => rows,
+        _ => return Err(NpyError::Shape { expected: N, found: header.shape }),
+    };
+
+    let data = &bytes[data_start..];
+    let needed = rows.checked_mul(N * T::SIZE).ok_or_else(|| header_error("shape is too big"))?;
+    if data.len() != needed {
+        return Err(NpyError::Header(format!("expected {} bytes of data for shape {:?} but found {}", needed, header.shape, data.len())));
+    }
+
+    let element = |k: usize| T::read_bytes(&data[k * T::SIZE..(k + 1) * T::SIZE], endian);
+    Ok((0..rows)
+        .map(|i| {
+            NVec {
+                components: core::array::from_fn(|j| {
+                    if header.fortran_order {
+                        element(j * rows + i) //column major - walk down the columns first
+                    } else {
+                        element(i * N + j)
+                    }
+                }),
+            }
+        })
+        .collect())
+}
+
+// Only the few python literal forms numpy puts in a header - 'strings', True/False and (tuples, of, ints,)
+fn parse_header(text: &str) -> Result<Header, NpyError> {
+    let mut p = HeaderParser { s: text.trim_end(), pos: 0 };
+    let (mut descr, mut fortran_order, mut shape) = (None, None, None);
+
+    p.expect('{')?;
+    loop {
+        p.skip_whitespace();
+        if p.eat('}') {
+            break;
+        }
+        let key = p.string()?;
+        p.expect(':')?;
+        p.skip_whitespace();
+        match key.as_str() {
+            "descr" if p.peek() == Some('[') => return Err(p.error("structured dtypes aren't supported")),
+            "descr" => descr = Some(p.string()?),
+            "fortran_order" => fortran_order = Some(p.boolean()?),
+            "shape" => shape = Some(p.tuple()?),
+            _ => return Err(p.error(&format!("unexpected key '{}'", key))),
+        }
+        p.skip_whitespace();
+        if !p.eat(',') {
+            p.expect('}')?;
+            break;
+        }
+    }
+
+    match (descr, fortran_order, shape) {
+        (Some(descr), Some(fortran_order), Some(shape)) => Ok(Header { descr, fortran_order, shape }),
+        _ => Err(NpyError::Header("header needs 'descr', 'fortran_order' and 'shape'".to_string())),
+    }
+}
+
+struct HeaderParser<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl HeaderParser<'_> {
+    fn error(&self, message: &str) -> NpyError {
+        NpyError::Header(format!("{} at byte {} of `{}`", message, self.pos, self.s))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.s[self.pos..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), NpyError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", c)))
+        }
+    }
+
+    fn string(&mut self) -> Result<String, NpyError> {
+        self.skip_whitespace();
+        let quote = match self.peek() {
+            Some(q @ ('\'' | '"')) => q,
+            _ => return Err(self.error("expected a string")),
+        };
+        self.pos += 1;
+        let end = self.s[self.pos..].find(quote).ok_or_else(|| self.error("unterminated string"))?;
+        let string = self.s[self.pos..self.pos + end].to_string();
+        self.pos += end + 1;
+        Ok(string)
+    }
+
+    fn boolean(&mut self) -> Result<bool, NpyError> {
+        for (word, value) in [("True", true), ("False", false)] {
+            if self.s[self.pos..].starts_with(word) {
+                self.pos += word.len();
+                return Ok(value);
+            }
+        }
+        Err(self.error("expected True or False"))
+    }
+
+    fn tuple(&mut self) -> Result<Vec<usize>, NpyError> {
+        self.expect('(')?;
+        let mut items = Vec::new();
+        loop {
+            if self.eat(')') {
+                return Ok(items);
+            }
+            let digits = self.s[self.pos..].chars().take_while(|c| c.is_ascii_digit()).count();
+            let item = self.s[self.pos..self.pos + digits].parse().map_err(|_| self.error("expected a dimension"))?;
+            self.pos += digits;
+            items.push(item);
+            if !self.eat(',') {
+                self.expect(')')?;
+                return Ok(items);
+            }
+        }
+    }
+}
+
+
+
+// Writing //
+
+pub fn write_npy<W: Write, T: NpyElement, const N: usize>(mut writer: W, vecs: &[NVec<T, N>], options: NpyOptions) -> io::Result<()> {
+    let shape = format!("({}, {})", vecs.len(), N);
+    let dict = format!(
+        "{{'descr': '{}', 'fortran_order': {}, 'shape': {}, }}",
+        T::descr(options.endian),
+        if options.fortran_order { "True" } else { "False" },
+        shape
+    );
+
+    // numpy pads the header with spaces so the data starts on a multiple of 64 bytes, ending in a newline
+    let (version, prefix) = if dict.len() + 1 + 10 <= u16::MAX as usize { (1u8, 10) } else { (2u8, 12) };
+    let header_len = (dict.len() + 1 + prefix).div_ceil(64) * 64 - prefix;
+    let mut header = dict.into_bytes();
+    header.resize(header_len - 1, b' ');
+    header.push(b'\n');
+
+    let mut out = Vec::with_capacity(prefix + header_len + vecs.len() * N * T::SIZE);
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&[version, 0]);
+    if version == 1 {
+        out.extend_from_slice(&(header_len as u16).to_le_bytes());
+    } else {
+        out.extend_from_slice(&(header_len as u32).to_le_bytes());
+    }
+    out.extend_from_slice(&header);
+
+    if options.fortran_order {
+        for j in 0..N {
+            for v in vecs {
+                v.components[j].write_bytes(options.endian, &mut out);
+            }
+        }
+    } else {
+        for v in vecs {
+            for c in v.components {
+                c.write_bytes(options.endian, &mut out);
+            }
+        }
+    }
+
+    writer.write_all(&out)?;
+    writer.flush()
+}
+
+
+
+// .npz archives //
+// A zip file of .npy files, one per array, named after the keyword given to np.savez (or arr_0, arr_1...)
+// np.savez always writes zip64 records, so those have to be understood even for small files
+
+pub struct Npz {
+    arrays: Vec<(String, Vec<u8>)>,
+}
+
+impl Npz {
+    // array names without the .npy on the end, in the order they're stored
+    pub fn names(&self) -> Vec<&str> {
+        self.arrays.iter().map(|(name, _)| name.trim_end_matches(".npy")).collect()
+    }
+
+    // works with or without the .npy
+    pub fn get<T: NpyElement, const N: usize>(&self, name: &str) -> Result<Vec<NVec<T, N>>, NpyError> {
+        let file = if name.ends_with(".npy") { name.to_string() } else { format!("{}.npy", name) };
+        match self.arrays.iter().find(|(n, _)| *n == file) {
+            Some((_, bytes)) => parse_npy(bytes),
+            None => Err(NpyError::MissingArray(name.to_string())),
+        }
+    }
+}
+
+pub fn read_npz<R: Read>(mut reader: R) -> Result<Npz, NpyError> {
+    let mut zip = Vec::new();
+    reader.read_to_end(&mut zip)?;
+    let archive_error = |message: &str| NpyError::Archive(message.to_string());
+
+    // every offset and size comes from the file, so a crafted one could point anywhere - the sums are checked so they can't wrap
+    let bytes_at = |at: usize, len: u64| {
+        usize::try_from(len).ok().and_then(|len| at.checked_add(len)).and_then(|end| zip.get(at..end)).ok_or_else(|| archive_error("truncated"))
+    };
+    let u16_at = |at: usize| bytes_at(at, 2).map(|b| u16::from_le_bytes([b[0], b[1]]) as usize);
+    let u32_at = |at: usize| bytes_at(at, 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as u64);
+    let u64_at = |at: usize| bytes_at(at, 8).map(|b| u64::from_le_bytes(b.try_into().unwrap_or_else(|_| unreachable!())));
+    let offset = |x: u64| usize::try_from(x).map_err(|_| archive_error("truncated"));
+
+    // the end of central directory record is the last thing in the file, apart from a comment of up to 64k
+    let eocd = (0..zip.len().saturating_sub(21))
+        .rev()
+        .take(22 + u16::MAX as usize)
+        .find(|&at| zip[at..].starts_with(&[0x50, 0x4b, 0x05, 0x06]))
+        .ok_or_else(|| archive_error("no end of central directory record, is this a zip file?"))?;
+    let (mut count, mut directory) = (u16_at(eocd + 10)? as u64, u32_at(eocd + 16)?);
+
+    if count == 0xffff || directory == 0xffff_ffff {
+        // zip64 - the real values are in another record, pointed to by the locator just before this one
+        let locator = eocd.checked_sub(20).filter(|&at| zip[at..].starts_with(&[0x50, 0x4b, 0x06, 0x07]));
+        let record = offset(u64_at(locator.ok_or_else(|| archive_error("missing zip64 locator"))? + 8)?)?;
+        if !zip.get(record..).is_some_and(|r| r.starts_with(&[0x50, 0x4b, 0x06, 0x06])) {
+            return Err(archive_error("missing zip64 end of central directory record"));
+        }
+        count = u64_at(record + 32)?;
+        directory = u64_at(record + 48)?;
+    }
+
+    let mut arrays = Vec::new();
+    let mut at = offset(directory)?;
+    for _ in 0..count {
+        if !zip.get(at..).is_some_and(|r| r.starts_with(&[0x50, 0x4b, 0x01, 0x02])) {
+            return Err(archive_error("bad central directory entry"));
+        }
+        let method = u16_at(at + 10)?;
+        let crc = u32_at(at + 16)? as u32;
+        let (mut size, mut uncompressed, mut local) = (u32_at(at + 20)?, u32_at(at + 24)?, u32_at(at + 42)?);
+        let (name_len, extra_len, comment_len) = (u16_at(at + 28)?, u16_at(at + 30)?, u16_at(at + 32)?);
+        let name = bytes_at(at + 46, name_len as u64)?;
+        let name = String::from_utf8_lossy(name).into_owned();
+
+        // the zip64 extra field holds whichever of the sizes and offset didn't fit, in that order
+        let mut extra = at + 46 + name_len;
+        let extra_end = extra + extra_len;
+        while extra + 4 <= extra_end {
+            let (id, len) = (u16_at(extra)?, u16_at(extra + 2)?);
+            if id == 0x0001 {
+                let mut field = extra + 4;
+                for value in [&mut uncompressed, &mut size, &mut local] {
+                    if *value == 0xffff_ffff {
+                        *value = u64_at(field)?;
+                        field += 8;
+                    }
+                }
+            }
+            extra += 4 + len;
+        }
+
+        if method != 0 {
+            return Err(NpyError::Archive(format!("`{}` is compressed, only stored archives (np.savez, not np.savez_compressed) can be read", name)));
+        }
+        if size != uncompressed {
+            return Err(NpyError::Archive(format!("`{}` is stored but its sizes don't match", name)));
+        }
+
+        // the data follows the local header, whose extra field can differ from the central one
+        let local = offset(local)?;
+        if !zip.get(local..).is_some_and(|r| r.starts_with(&[0x50, 0x4b, 0x03, 0x04])) {
+            return Err(NpyError::Archive(format!("bad local header for `{}`", name)));
+        }
+        let start = local + 30 + u16_at(local + 26)? + u16_at(local + 28)?;
+        let data = bytes_at(start, size)?;
+        if crc32(data) != crc {
+            return Err(NpyError::Archive(format!("checksum mismatch for `{}`", name)));
+        }
+
+        arrays.push((name, data.to_vec()));
+        at += 46 + name_len + extra_len + comment_len;
+    }
+
+    Ok(Npz { arrays })
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // every numerical! type, both byte orders, both layouts
+    macro_rules! round_trip {
+        ($($type:ty), *) => {
+            $(
+                let vecs: Vec<NVec<$type, 3>> = (0..5u8).map(|i| NVec::new([i as $type, (i + 10) as $type, (i * 20) as $type])).collect();
+                for endian in [Endian::Little, Endian::Big] {
+                    for fortran_order in [false, true] {
+                        let mut file = Vec::new();
+                        write_npy(&mut file, &vecs, NpyOptions { endian, fortran_order }).unwrap();
+                        assert_eq!(read_npy::<_, $type, 3>(&file[..]).unwrap(), vecs);
+                    }
+                }
+            )*
+        };
+    }
+
+    #[test]
+    fn round_trip_test() {
+        println!("\n\nTesting for .npy write then read\n\n");
+        round_trip!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, f32, f64);
+
+        let empty: Vec<NVec<f32, 2>> = Vec::new();
+        let mut file = Vec::new();
+        write_npy(&mut file, &empty, NpyOptions::default()).unwrap();
+        assert_eq!(read_npy::<_, f32, 2>(&file[..]).unwrap(), empty);
+    }
+
+    #[test]
+    fn numpy_layout_test() {
+        println!("\n\nTesting for reading .npy bytes laid out as numpy writes them\n\n");
+        // what np.save writes for np.array([[1, 2, 3], [4, 5, 6]], dtype='<i4')
+        let mut file = Vec::new();
+        write_npy(&mut file, &[NVec::new([1i32, 2, 3]), NVec::new([4, 5, 6])], NpyOptions::default()).unwrap();
+        let header = "{'descr': '<i4', 'fortran_order': False, 'shape': (2, 3), }";
+        assert_eq!(&file[..10], b"\x93NUMPY\x01\x00\x76\x00");
+        assert_eq!(&file[10..10 + header.len()], header.as_bytes());
+        assert_eq!(file[127], b'\n');
+        assert_eq!(&file[128..136], &[1, 0, 0, 0, 2, 0, 0, 0]);
+
+        // the same array, hand made as big endian Fortran order with a version 2 header
+        let header = "{\"descr\":\">i4\",\"fortran_order\":True,\"shape\":(2,3)}\n";
+        let mut v2 = b"\x93NUMPY\x02\x00".to_vec();
+        v2.extend_from_slice(&(header.len() as u32).to_le_bytes());
+        v2.extend_from_slice(header.as_bytes());
+        for x in [1i32, 4, 2, 5, 3, 6] {
+            v2.extend_from_slice(&x.to_be_bytes());
+        }
+        assert_eq!(read_npy::<_, i32, 3>(&v2[..]).unwrap(), vec![NVec::new([1, 2, 3]), NVec::new([4, 5, 6])]);
+    }
+
+    #[test]
+    fn mismatch_test() {
+        println!("\n\nTesting for .npy dtype and shape errors\n\n");
+        let mut file = Vec::new();
+        write_npy(&mut file, &[NVec::new([1.0f64, 2.0])], NpyOptions::default()).unwrap();
+
+        let err = read_npy::<_, f32, 2>(&file[..]).unwrap_err();
+        assert!(matches!(&err, NpyError::Dtype { expected, found } if expected == "<f4" && found == "<f8"));
+        assert_eq!(err.to_string(), "dtype mismatch: expected '<f4' but the array is '<f8'");
+        assert!(matches!(read_npy::<_, i64, 2>(&file[..]), Err(NpyError::Dtype { .. })));
+
+        let err = read_npy::<_, f64, 3>(&file[..]).unwrap_err();
+        assert!(matches!(&err, NpyError::Shape { expected: 3, found } if found == &[1, 2]));
+        assert_eq!(err.to_string(), "shape mismatch: expected (M, 3) but the array is [1, 2]");
+
+        assert!(matches!(read_npy::<_, f64, 2>(&file[..file.len() - 1]), Err(NpyError::Header(_))));
+        assert!(matches!(read_npy::<_, f64, 2>(&b"PK\x03\x04"[..]), Err(NpyError::Header(_))));
+    }
+
+    // a stored zip the way python's zipfile writes one for np.savez, zip64 extras and all
+    fn stored_zip(files: &[(&str, &[u8])], method: u16) -> Vec<u8> {
+        let (mut zip, mut directory) = (Vec::new(), Vec::new());
+        for (name, data) in files {
+            let offset = zip.len() as u64;
+            let fields = |sig: u32, out: &mut Vec<u8>| {
+                out.extend_from_slice(&sig.to_le_bytes());
+                if sig == 0x0201_4b50 {
+                    out.extend_from_slice(&45u16.to_le_bytes()); //made by
+                }
+                out.extend_from_slice(&45u16.to_le_bytes()); //version needed
+                out.extend_from_slice(&[0, 0]); //flags
+                out.extend_from_slice(&method.to_le_bytes());
+                out.extend_from_slice(&[0, 0, 0x21, 0]); //time and date
+                out.extend_from_slice(&crc32(data).to_le_bytes());
+                out.extend_from_slice(&[0xff; 8]); //sizes are in the zip64 extra
+                out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+                out.extend_from_slice(&20u16.to_le_bytes());
+            };
+
+            fields(0x0403_4b50, &mut zip);
+            zip.extend_from_slice(name.as_bytes());
+            zip.extend_from_slice(&[1, 0, 16, 0]);
+            zip.extend_from_slice(&(data.len() as u64).to_le_bytes());
+            zip.extend_from_slice(&(data.len() as u64).to_le_bytes());
+            zip.extend_from_slice(data);
+
+            fields(0x0201_4b50, &mut directory);
+            directory.extend_from_slice(&[0; 6]); //comment length, disk, internal attributes
+            directory.extend_from_slice(&[0; 4]); //external attributes
+            directory.extend_from_slice(&(offset as u32).to_le_bytes());
+            directory.extend_from_slice(name.as_bytes());
+            directory.extend_from_slice(&[1, 0, 16, 0]);
+            directory.extend_from_slice(&(data.len() as u64).to_le_bytes());
+            directory.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        }
+        let start = zip.len() as u32;
+        zip.extend_from_slice(&directory);
+        zip.extend_from_slice(&[0x50, 0x4b, 0x05, 0x06, 0, 0, 0, 0]);
+        zip.extend_from_slice(&(files.len() as u16).to_le_bytes());
+        zip.extend_from_slice(&(files.len() as u16).to_le_bytes());
+        zip.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+        zip.extend_from_slice(&start.to_le_bytes());
+        zip.extend_from_slice(&[0, 0]);
+        zip
+    }
+
+    #[test]
+    fn npz_test() {
+        println!("\n\nTesting for reading .npz archives\n\n");
+        let points = vec![NVec::new([1.0f64, 2.0, 3.0]), NVec::new([-4.0, 5.5, 6.0])];
+        let labels = vec![NVec::new([7u8]), NVec::new([9])];
+        let (mut a, mut b) = (Vec::new(), Vec::new());
+        write_npy(&mut a, &points, NpyOptions::default()).unwrap();
+        write_npy(&mut b, &labels, NpyOptions { endian: Endian::Big, fortran_order: true }).unwrap();
+
+        let npz = read_npz(&stored_zip(&[("points.npy", &a), ("arr_1.npy", &b)], 0)[..]).unwrap();
+        assert_eq!(npz.names(), vec!["points", "arr_1"]);
+        assert_eq!(npz.get::<f64, 3>("points").unwrap(), points);
+        assert_eq!(npz.get::<u8, 1>("arr_1.npy").unwrap(), labels);
+        assert!(matches!(npz.get::<f64, 3>("nope"), Err(NpyError::MissingArray(_))));
+        assert!(matches!(npz.get::<f32, 3>("points"), Err(NpyError::Dtype { .. })));
+
+        assert!(matches!(read_npz(&stored_zip(&[("points.npy", &a)], 8)[..]), Err(NpyError::Archive(m)) if m.contains("compressed")));
+        let mut corrupt = stored_zip(&[("points.npy", &a)], 0);
+        corrupt[200] ^= 1;
+        assert!(matches!(read_npz(&corrupt[..]), Err(NpyError::Archive(m)) if m.contains("checksum")));
+        assert!(matches!(read_npz(&a[..]), Err(NpyError::Archive(_))));
+
+        // a crafted zip64 size that would run past the end of memory, not just the end of the file
+        let mut huge = stored_zip(&[("points.npy", &a)], 0);
+        let directory = u32::from_le_bytes(huge[huge.len() - 6..huge.len() - 2].try_into().unwrap()) as usize;
+        let sizes = directory + 46 + "points.npy".len() + 4;
+        huge[sizes..sizes + 16].copy_from_slice(&[[0xf0; 8], [0xf0; 8]].concat());
+        assert!(matches!(read_npz(&huge[..]), Err(NpyError::Archive(m)) if m == "truncated"));
+        huge[sizes..sizes + 16].copy_from_slice(&[0xff; 16]);
+        assert!(matches!(read_npz(&huge[..]), Err(NpyError::Archive(m)) if m == "truncated"));
+
+        // cut short anywhere, or with any byte flipped, it's an error rather than a panic
+        let whole = stored_zip(&[("points.npy", &a), ("arr_1.npy", &b)], 0);
+        for len in 0..whole.len() {
+            assert!(read_npz(&whole[..len]).is_err());
+        }
+        for i in 0..whole.len() {
+            for flip in [0x01, 0x80, 0xff] {
+                let mut bad = whole.clone();
+                bad[i] ^= flip;
+                let _ = read_npz(&bad[..]);
+            }
+        }
+    }
+}
@@ -0,0 +1,349 @@
+use super::*;
+use std::fmt;
+use std::str::FromStr;
+
+// Reading NVecs back out of text (config files, logs, whatever Display wrote)
+// Accepts any of
+//     [1, 2, 3]     (1 2 3)     1,2,3     [ 1,2 , 3 ]
+// i.e. optional matching brackets, components separated by a comma, whitespace, or both
+// Each component is parsed with the component type's own FromStr, so "1.5" is an error for an integer NVec
+// Errors are NVecError::Parse with the byte offset of the problem, e.g. the start of the bad component
+//
+// Display writes the [1, 2, 3] form and Rust prints floats in their shortest form that reads back to the same bits,
+// so s.parse::<NVec<T, N>>() gives back exactly the vector that was printed (NaN aside, since NaN != NaN)
+
+impl<T, const N: usize> FromStr for NVec<T, N>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    type Err = NVecError;
+
+    fn from_str(s: &str) -> Result<Self, NVecError> {
+        Parser { s, pos: 0 }.nvec()
+    }
+}
+
+// Finite floats read like any other float, but NaN and inf are turned away
+macro_rules! finite_from_str {
+    ($($type:ty), *) => {
+        $(
+            impl FromStr for Finite<$type> {
+                type Err = NVecError;
+
+                fn from_str(s: &str) -> Result<Self, NVecError> {
+                    let x: $type = s.parse().map_err(|e| error(0, format!("{}", e)))?;
+                    Finite::<$type>::new(x).map_err(|_| error(0, format!("`{}` is not finite", s)))
+                }
+            }
+        )*
+    };
+}
+
+finite_from_str!(f32, f64);
+
+// Half floats go through f32 and round to nearest, the same as from_f32
+// Display prints the exact f32 value, so anything printed reads back to the same bits
+macro_rules! half_from_str {
+    ($($type:ident), *) => {
+        $(
+            impl FromStr for $type {
+                type Err = NVecError;
+
+                fn from_str(s: &str) -> Result<Self, NVecError> {
+                    s.parse::<f32>().map($type::from_f32).map_err(|e| error(0, format!("{}", e)))
+                }
+            }
+        )*
+    };
+}
+
+half_from_str!(F16, BF16);
+
+// Intervals are lo..hi as Display writes them, or a single number for a point interval
+// NaN..NaN is the empty interval, any other NaN or a lo above hi is an error rather than the panic Interval::new gives
+macro_rules! interval_from_str {
+    ($($type:ty), *) => {
+        $(
+            impl FromStr for Interval<$type> {
+                type Err = NVecError;
+
+                fn from_str(s: &str) -> Result<Self, NVecError> {
+                    let bound = |t: &str, position: usize| t.parse::<$type>().map_err(|e| error(position, format!("`{}` is not a valid bound: {}", t, e)));
+                    let Some((lo, hi)) = s.split_once("..") else {
+                        return Ok(Interval::point(bound(s, 0)?));
+                    };
+                    let (lo, hi) = (bound(lo, 0)?, bound(hi, lo.len() + 2)?);
+                    if lo.is_nan() && hi.is_nan() {
+                        Ok(Interval::<$type>::empty())
+                    } else if lo <= hi {
+                        Ok(Interval { lo, hi })
+                    } else {
+                        Err(error(0, format!("`{}` has its lower bound above its upper bound", s)))
+                    }
+                }
+            }
+        )*
+    };
+}
+
+interval_from_str!(f32, f64);
+
+fn error(position: usize, message: String) -> NVecError {
+    NVecError::Parse { position, message }
+}
+
+struct Parser<'a> {
+    s: &'a str,
+    pos: usize, //byte offset of the next unread char
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.s[self.pos..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek().filter(|c| c.is_whitespace()) {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    // what to call the next char in an error message
+    fn found(&self) -> String {
+        match self.peek() {
+            Some(c) => format!("`{}`", c),
+            None => "the end of the input".to_string(),
+        }
+    }
+
+    fn nvec<T, const N: usize>(mut self) -> Result<NVec<T, N>, NVecError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.skip_whitespace();
+        let close = match self.peek() {
+            Some('[') => Some(']'),
+            Some('(') => Some(')'),
+            _ => None,
+        };
+        if close.is_some() {
+            self.pos += 1;
+        }
+
+        let mut components = Vec::with_capacity(N);
+        loop {
+            self.skip_whitespace();
+            if components.is_empty() && self.at_end_of_list() {
+                break; //nothing in the list at all, only fine when N == 0
+            }
+
+            let start = self.pos;
+            let token = self.token();
+            if token.is_empty() {
+                return Err(error(start, format!("expected a number but found {}", self.found())));
+            }
+            if components.len() == N {
+                return Err(error(start, format!("too many components, expected {}", N)));
+            }
+            let c = token.parse::<T>().map_err(|e| {
+                error(start, format!("`{}` is not a valid {}: {}", token, std::any::type_name::<T>(), e))
+            })?;
+            components.push(c);
+
+            // a comma, some whitespace or both between components
+            self.skip_whitespace();
+            if self.peek() == Some(',') {
+                self.pos += 1;
+            } else if self.at_end_of_list() {
+                break;
+            } else if self.pos == start + token.len() {
+                return Err(error(self.pos, format!("expected `,` or whitespace but found {}", self.found())));
+            }
+        }
+
+        if components.len() < N {
+            return Err(error(self.pos, format!("expected {} components but found {}", N, components.len())));
+        }
+
+        if let Some(close) = close {
+            if self.peek() != Some(close) {
+                return Err(error(self.pos, format!("expected `{}` but found {}", close, self.found())));
+            }
+            self.pos += 1;
+        }
+
+        self.skip_whitespace();
+        if self.peek().is_some() {
+            return Err(error(self.pos, format!("unexpected {} after the vector", self.found())));
+        }
+
+        Ok(NVec::try_from(components).unwrap_or_else(|_| unreachable!())) //exactly N, checked above
+    }
+
+    // any closing bracket ends the list, whether it's the right one gets checked after
+    fn at_end_of_list(&self) -> bool {
+        matches!(self.peek(), None | Some(']') | Some(')'))
+    }
+
+    // everything up to the next separator or bracket
+    fn token(&mut self) -> &'a str {
+        let start = self.pos;
+        while let Some(c) = self.peek().filter(|c| !c.is_whitespace() && !matches!(c, ',' | '[' | ']' | '(' | ')')) {
+            self.pos += c.len_utf8();
+        }
+        &self.s[start..self.pos]
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn position<T: std::fmt::Debug>(r: Result<T, NVecError>) -> (usize, String) {
+        match r {
+            Err(NVecError::Parse { position, message }) => (position, message),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn forms_test() {
+        println!("\n\nTesting for parsing NVecs\n\n");
+        let expected = NVec::new([1, -2, 3]);
+        for s in ["[1, -2, 3]", "(1 -2 3)", "1,-2,3", "  [ 1 ,-2,   3 ]  ", "(1,\t-2\n3)", "1 -2 3", "[+1, -2, 3]"] {
+            assert_eq!(s.parse::<NVec<i32, 3>>(), Ok(expected), "{}", s);
+        }
+
+        assert_eq!("[1.5, 2e3, -0.25]".parse(), Ok(NVec::new([1.5f32, 2000.0, -0.25])));
+        assert_eq!("(255 0)".parse(), Ok(NVec::new([255u8, 0])));
+        assert_eq!("[170141183460469231731687303715884105727]".parse(), Ok(NVec::new([i128::MAX])));
+        assert_eq!("[inf, -inf]".parse(), Ok(NVec::new([f64::INFINITY, f64::NEG_INFINITY])));
+        assert_eq!("[]".parse(), Ok(NVec::<f64, 0>::new([])));
+        assert_eq!("  ".parse(), Ok(NVec::<u16, 0>::new([])));
+
+        let f: NVec<Finite<f64>, 2> = "[0.5, 2]".parse().unwrap();
+        assert_eq!(f.into_inner(), NVec::new([0.5, 2.0]));
+        assert!("[0.5, NaN]".parse::<NVec<Finite<f64>, 2>>().is_err());
+    }
+
+    #[test]
+    fn error_position_test() {
+        println!("\n\nTesting for parse error positions\n\n");
+        let (p, m) = position("[1, 2.5, 3]".parse::<NVec<i32, 3>>());
+        assert_eq!(p, 4);
+        assert!(m.starts_with("`2.5` is not a valid i32"), "{}", m);
+
+        assert_eq!(position("[1, 256]".parse::<NVec<u8, 2>>()).0, 4);
+        assert_eq!(position("[1, 2]".parse::<NVec<f64, 3>>()), (5, "expected 3 components but found 2".to_string()));
+        assert_eq!(position("[1, 2, 3, 4]".parse::<NVec<f64, 3>>()), (10, "too many components, expected 3".to_string()));
+        assert_eq!(position("[1, 2)".parse::<NVec<f64, 2>>()), (5, "expected `]` but found `)`".to_string()));
+        assert_eq!(position("(1 2".parse::<NVec<f64, 2>>()), (4, "expected `)` but found the end of the input".to_string()));
+        assert_eq!(position("[1,, 2]".parse::<NVec<f64, 2>>()), (3, "expected a number but found `,`".to_string()));
+        assert_eq!(position("[1, 2] x".parse::<NVec<f64, 2>>()), (7, "unexpected `x` after the vector".to_string()));
+        assert_eq!(position("1 2]".parse::<NVec<f64, 2>>()).0, 3);
+        assert_eq!(position("".parse::<NVec<f64, 1>>()).0, 0);
+        assert_eq!(position("[é, 1]".parse::<NVec<f64, 2>>()).0, 1);
+        assert_eq!(position("[1é, 1]".parse::<NVec<f64, 2>>()).0, 1);
+    }
+
+    // Display then parse gives back the exact same bits
+    macro_rules! round_trip {
+        ($rng:expr, $($type:ty), *) => {
+            $(
+                for _ in 0..200 {
+                    let v: NVec<$type, 4> = NVec::new([$rng.gen(), $rng.gen(), $rng.gen(), $rng.gen()]);
+                    assert_eq!(v.to_string().parse::<NVec<$type, 4>>(), Ok(v));
+                }
+            )*
+        };
+    }
+
+    #[test]
+    fn round_trip_test() {
+        println!("\n\nTesting for Display/FromStr round trips\n\n");
+        let mut rng = StdRng::seed_from_u64(47);
+        round_trip!(rng, i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
+        // floats spread over the whole exponent range, not just [0, 1)
+        for _ in 0..1000 {
+            let v: NVec<f64, 3> = NVec::new(core::array::from_fn(|_| f64::from_bits(rng.gen())));
+            let w: NVec<f32, 3> = NVec::new(core::array::from_fn(|_| f32::from_bits(rng.gen())));
+            if v.iter().all(|c| !c.is_nan()) {
+                assert_eq!(v.to_string().parse::<NVec<f64, 3>>(), Ok(v));
+            }
+            if w.iter().all(|c| !c.is_nan()) {
+                assert_eq!(w.to_string().parse::<NVec<f32, 3>>(), Ok(w));
+            }
+        }
+        assert_eq!(NVec::new([0.1, -0.0, 1e-300]).to_string().parse(), Ok(NVec::new([0.1, -0.0, 1e-300])));
+
+        // every half float, NaNs aside
+        for bits in 0..=u16::MAX {
+            let (h, b) = (F16::from_bits(bits), BF16::from_bits(bits));
+            if !h.to_f32().is_nan() {
+                assert_eq!(h.to_string().parse::<F16>().map(F16::to_bits), Ok(bits));
+            }
+            if !b.to_f32().is_nan() {
+                assert_eq!(b.to_string().parse::<BF16>().map(BF16::to_bits), Ok(bits));
+            }
+        }
+        let h: NVec<F16, 3> = NVec::new([1.5f32, -0.1, 65504.0]).to_f16();
+        assert_eq!(h.to_string().parse::<NVec<F16, 3>>().map(|v| v.components.map(F16::to_bits)), Ok(h.components.map(F16::to_bits)));
+
+        // intervals, including ones from outward rounding whose bounds are a few ulps apart
+        for _ in 0..1000 {
+            let (x, y): (f64, f64) = (rng.gen_range(-1e6..1e6), rng.gen_range(-1e6..1e6));
+            let v = NVec::new([Interval::point(x) + Interval::point(y), Interval::point(x).sqrt(), Interval::new(x.min(y), x.max(y))]);
+            let w: NVec<Interval<f32>, 1> = NVec::new([Interval::point(x as f32) * Interval::point(y as f32)]);
+            let read = v.to_string().parse::<NVec<Interval<f64>, 3>>().unwrap();
+            for (a, b) in read.iter().zip(v.iter()) {
+                assert!(a == b || (a.is_empty() && b.is_empty()), "{} read back as {}", v, read);
+            }
+            assert_eq!(w.to_string().parse::<NVec<Interval<f32>, 1>>(), Ok(w));
+        }
+        assert_eq!("[0.5..0.75, -1..1, 2]".parse(), Ok(NVec::new([Interval::new(0.5, 0.75), Interval::new(-1.0, 1.0), Interval::point(2.0)])));
+        assert!("NaN..NaN".parse::<Interval<f64>>().unwrap().is_empty());
+        assert_eq!("2..1".parse::<Interval<f64>>(), Err(error(0, "`2..1` has its lower bound above its upper bound".to_string())));
+        assert_eq!(position("-1..x".parse::<Interval<f32>>()).0, 4);
+    }
+
+    #[test]
+    fn malformed_input_test() {
+        println!("\n\nTesting for parsing random malformed input\n\n");
+        // random strings built from the characters that matter, plus some that don't belong
+        let alphabet: Vec<char> = "[]() ,\t0123456789.-+eEinfNa_é".chars().collect();
+        let mut rng = StdRng::seed_from_u64(1047);
+        for _ in 0..5000 {
+            let len = rng.gen_range(0..16);
+            let s: String = (0..len).map(|_| alphabet[rng.gen_range(0..alphabet.len())]).collect();
+            match s.parse::<NVec<f64, 3>>() {
+                Ok(v) => assert_eq!(v.to_string().parse::<NVec<f64, 3>>().map(|w| w.to_string()), Ok(v.to_string())),
+                Err(NVecError::Parse { position, .. }) => assert!(position <= s.len() && s.is_char_boundary(position), "{:?}", s),
+                Err(e) => panic!("{:?} gave {:?}", s, e),
+            }
+            let _ = s.parse::<NVec<u8, 2>>(); //only checking integers don't panic either
+        }
+
+        // single character edits of a valid vector
+        let valid = "[1.5, -2, 3e1]";
+        for _ in 0..2000 {
+            let mut chars: Vec<char> = valid.chars().collect();
+            let i = rng.gen_range(0..chars.len());
+            match rng.gen_range(0..3) {
+                0 => { chars.remove(i); }
+                1 => chars.insert(i, alphabet[rng.gen_range(0..alphabet.len())]),
+                _ => chars[i] = alphabet[rng.gen_range(0..alphabet.len())],
+            }
+            let s: String = chars.into_iter().collect();
+            if let Err(NVecError::Parse { position, .. }) = s.parse::<NVec<f32, 3>>() {
+                assert!(position <= s.len(), "{:?}", s);
+            }
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use super::*;
+use num_traits::Float;
+
+// Points vs vectors - an NVec is a displacement, a Point is a position
+// The allowed ops follow affine space rules:
+//     Point - Point = NVec   (the displacement between them)
+//     Point + NVec  = Point  (moving a position)
+//     Point - NVec  = Point
+//     Point + Point          doesn't compile - adding two positions means nothing
+// Same promotion rules as add.rs/sub.rs, so a Point<i32, N> + NVec<f64, N> gives a Point<f64, N>
+
+/// A position in N dimensional space.
+///
+/// Adding two points is rejected at compile time:
+///
+/// ```compile_fail
+/// use nvecslib::*;
+/// let a = Point::new([1.0, 2.0]);
+/// let b = Point::new([3.0, 4.0]);
+/// let c = a + b;
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Point<T: Numerical, const N: usize> {
+    pub coords: NVec<T, N>,
+}
+
+impl<T: Numerical, const N: usize> Point<T, N> {
+    pub fn new(components: [T; N]) -> Self {
+        Point { coords: NVec::new(components) }
+    }
+
+    // the point you get by moving v away from the origin
+    pub fn from_vector(v: NVec<T, N>) -> Self {
+        Point { coords: v }
+    }
+
+    // the displacement from the origin to here
+    pub fn to_vector(self) -> NVec<T, N> {
+        self.coords
+    }
+}
+
+impl<T: Numerical + Default, const N: usize> Point<T, N> {
+    pub fn origin() -> Self {
+        Point::new([T::default(); N])
+    }
+}
+
+impl<T: Numerical, const N: usize> From<NVec<T, N>> for Point<T, N> {
+    fn from(v: NVec<T, N>) -> Self {
+        Point::from_vector(v)
+    }
+}
+
+impl<T, const N: usize> Point<T, N>
+where
+    T: Float + Numerical + AsPrimitive<T> + Promote<T, Output = T>,
+{
+    // affine combination sum(w_i p_i) with the weights normalised to sum to 1
+    // worked out as p_0 + sum(w_i (p_i - p_0)) so it never adds positions directly
+    // None if there are no points, the counts don't match or the weights sum to zero
+    pub fn barycentric(points: &[Point<T, N>], weights: &[T]) -> Option<Self> {
+        if points.is_empty() || points.len() != weights.len() {
+            return None;
+        }
+
+        let total = weights.iter().fold(T::zero(), |acc, w| acc + *w);
+        if total == T::zero() {
+            return None;
+        }
+
+        let origin = points[0];
+        let offset = points
+            .iter()
+            .zip(weights.iter())
+            .map(|(p, w)| (*p - origin) * (*w / total))
+            .fold(NVec::new([T::zero(); N]), |acc, elem| acc + elem);
+
+        Some(origin + offset)
+    }
+
+    // the average position - None for an empty slice
+    pub fn centroid(points: &[Point<T, N>]) -> Option<Self> {
+        Point::barycentric(points, &vec![T::one(); points.len()])
+    }
+}
+
+
+
+// Operations //
+
+// Point - Point = NVec
+impl<T, U, V, const N: usize> Sub<Point<U, N>> for Point<T, N>
+where
+    T: Numerical + AsPrimitive<V> + Promote<U, Output = V>,
+    U: Numerical + AsPrimitive<V>,
+    V: Numerical + Sub<Output = V> + 'static,
+{
+    type Output = NVec<V, N>;
+
+    fn sub(self, rhs: Point<U, N>) -> Self::Output {
+        self.coords - rhs.coords
+    }
+}
+
+// Point + NVec = Point
+impl<T, U, V, const N: usize> Add<NVec<U, N>> for Point<T, N>
+where
+    T: Numerical + AsPrimitive<V> + Promote<U, Output = V>,
+    U: Numerical + AsPrimitive<V>,
+    V: Numerical + Add<Output = V> + 'static,
+{
+    type Output = Point<V, N>;
+
+    fn add(self, rhs: NVec<U, N>) -> Self::Output {
+        Point { coords: self.coords + rhs }
+    }
+}
+
+// NVec + Point = Point
+impl<T, U, V, const N: usize> Add<Point<U, N>> for NVec<T, N>
+where
+    T: Numerical + AsPrimitive<V> + Promote<U, Output = V>,
+    U: Numerical + AsPrimitive<V>,
+    V: Numerical + Add<Output = V> + 'static,
+{
+    type Output = Point<V, N>;
+
+    fn add(self, rhs: Point<U, N>) -> Self::Output {
+        Point { coords: self + rhs.coords }
+    }
+}
+
+// Point - NVec = Point
+impl<T, U, V, const N: usize> Sub<NVec<U, N>> for Point<T, N>
+where
+    T: Numerical + AsPrimitive<V> + Promote<U, Output = V>,
+    U: Numerical + AsPrimitive<V>,
+    V: Numerical + Sub<Output = V> + 'static,
+{
+    type Output = Point<V, N>;
+
+    fn sub(self, rhs: NVec<U, N>) -> Self::Output {
+        Point { coords: self.coords - rhs }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_type_test() {
+        println!("\n\nTesting for point ops\n\n");
+        let a = Point::new([1, 2, 3]);
+        let b = Point::new([4, 6, 8]);
+        let v = NVec::new([1, 1, 1]);
+
+        assert_eq!(b - a, NVec::new([3, 4, 5]));
+        assert_eq!(a + v, Point::new([2, 3, 4]));
+        assert_eq!(v + a, Point::new([2, 3, 4]));
+        assert_eq!(a - v, Point::new([0, 1, 2]));
+        assert_eq!(a + (b - a), b);
+        assert_eq!(Point::<i32, 3>::origin() + v, Point::from(v));
+    }
+
+    #[test]
+    fn casting_test() {
+        println!("\n\nTesting for point casting\n\n");
+        let a: Point<f64, 3> = Point::new([1.01, -2.65, 3.4]);
+        let b: Point<i32, 3> = Point::new([4, -5, 200]);
+        let v: NVec<i32, 3> = NVec::new([4, -5, 200]);
+
+        assert_eq!(a - b, NVec::new([1.01 - 4f64, -2.65 - -5f64, 3.4 - 200f64]));
+        assert_eq!(a + v, Point::new([1.01 + 4f64, -2.65 + -5f64, 3.4 + 200f64]));
+        assert_eq!(b + NVec::new([0.5f32, 0.5, 0.5]), Point::new([4.5f32, -4.5, 200.5]));
+    }
+
+    #[test]
+    fn combination_test() {
+        println!("\n\nTesting for barycentric combinations\n\n");
+        let tri = [Point::new([0.0, 0.0]), Point::new([3.0, 0.0]), Point::new([0.0, 3.0])];
+
+        assert_eq!(Point::centroid(&tri), Some(Point::new([1.0, 1.0])));
+        assert_eq!(Point::barycentric(&tri, &[0.0, 1.0, 0.0]), Some(tri[1]));
+        assert_eq!(Point::barycentric(&tri, &[2.0, 1.0, 1.0]), Some(Point::new([0.75, 0.75]))); //weights get normalised
+        assert_eq!(Point::barycentric(&tri, &[1.0, -1.0, 0.0]), None);
+        assert_eq!(Point::<f64, 2>::centroid(&[]), None);
+    }
+}
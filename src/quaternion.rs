@@ -0,0 +1,410 @@
+use super::*;
+use num_traits::Float;
+
+// Quaternions for rotating NVec<T, 3>s
+// Stored as w + xi + yj + zk, with w the scalar part and (x, y, z) the vector part
+// Rotations only make sense for floats, so everything here leans on num_traits::Float rather than Numerical
+// Rotation matrices are plain row major [[T; 3]; 3] arrays since the crate doesn't have a matrix type (yet...)
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Quaternion<T> {
+    pub w: T,
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Float> Quaternion<T> {
+    pub fn new(w: T, x: T, y: T, z: T) -> Self {
+        Quaternion { w, x, y, z }
+    }
+
+    // the do nothing rotation
+    pub fn identity() -> Self {
+        Quaternion::new(T::one(), T::zero(), T::zero(), T::zero())
+    }
+
+    // builds a quaternion from a scalar part and a vector part
+    pub fn from_parts(w: T, v: NVec<T, 3>) -> Self
+    where
+        T: Numerical,
+    {
+        Quaternion::new(w, v.components[0], v.components[1], v.components[2])
+    }
+
+    // a pure quaternion - used to push a vector through the sandwich product
+    pub fn from_vector(v: NVec<T, 3>) -> Self
+    where
+        T: Numerical,
+    {
+        Quaternion::from_parts(T::zero(), v)
+    }
+
+    pub fn scalar(&self) -> T {
+        self.w
+    }
+
+    pub fn vector(&self) -> NVec<T, 3>
+    where
+        T: Numerical,
+    {
+        NVec::new([self.x, self.y, self.z])
+    }
+
+    // rotation of angle radians around axis, right hand rule - the axis doesn't need to be normalised
+    pub fn from_axis_angle(axis: NVec<T, 3>, angle: T) -> Self
+    where
+        T: Numerical,
+    {
+        let [x, y, z] = axis.components;
+        let len = (x * x + y * y + z * z).sqrt();
+
+        if len == T::zero() {
+            return Quaternion::identity(); //no axis to rotate about
+        }
+
+        let half = angle / (T::one() + T::one());
+        let s = half.sin() / len;
+
+        Quaternion::new(half.cos(), x * s, y * s, z * s)
+    }
+
+    // inverse of from_axis_angle - the angle comes back in [0, 2pi]
+    pub fn to_axis_angle(&self) -> (NVec<T, 3>, T)
+    where
+        T: Numerical,
+    {
+        let q = self.normalize();
+        let s = (q.x * q.x + q.y * q.y + q.z * q.z).sqrt();
+        let angle = (T::one() + T::one()) * s.atan2(q.w);
+
+        if s == T::zero() {
+            return (NVec::new([T::one(), T::zero(), T::zero()]), angle); //any axis will do for no rotation
+        }
+
+        (NVec::new([q.x / s, q.y / s, q.z / s]), angle)
+    }
+
+    // roll about x, pitch about y, yaw about z, applied in that order (intrinsic z-y'-x'', the usual aerospace convention)
+    pub fn from_euler(roll: T, pitch: T, yaw: T) -> Self {
+        let two = T::one() + T::one();
+        let (sr, cr) = (roll / two).sin_cos();
+        let (sp, cp) = (pitch / two).sin_cos();
+        let (sy, cy) = (yaw / two).sin_cos();
+
+        Quaternion::new(
+            cr * cp * cy + sr * sp * sy,
+            sr * cp * cy - cr * sp * sy,
+            cr * sp * cy + sr * cp * sy,
+            cr * cp * sy - sr * sp * cy,
+        )
+    }
+
+    // gives back (roll, pitch, yaw) - pitch is clamped to +-pi/2 at gimbal lock
+    pub fn to_euler(&self) -> (T, T, T) {
+        let q = self.normalize();
+        let one = T::one();
+        let two = one + one;
+
+        let roll = (two * (q.w * q.x + q.y * q.z)).atan2(one - two * (q.x * q.x + q.y * q.y));
+        let sin_pitch = (two * (q.w * q.y - q.z * q.x)).max(-one).min(one);
+        let pitch = sin_pitch.asin();
+        let yaw = (two * (q.w * q.z + q.x * q.y)).atan2(one - two * (q.y * q.y + q.z * q.z));
+
+        (roll, pitch, yaw)
+    }
+
+    // the shortest arc rotation taking the direction of from on to the direction of to
+    pub fn from_two_vectors(from: NVec<T, 3>, to: NVec<T, 3>) -> Self
+    where
+        T: Numerical,
+    {
+        let [ax, ay, az] = from.components;
+        let [bx, by, bz] = to.components;
+
+        let dot = ax * bx + ay * by + az * bz;
+        let lens = ((ax * ax + ay * ay + az * az) * (bx * bx + by * by + bz * bz)).sqrt();
+
+        if lens == T::zero() {
+            return Quaternion::identity();
+        }
+
+        if dot / lens <= -T::one() + T::epsilon() {
+            // pointing opposite ways - any perpendicular axis works, so pick one away from the smallest component
+            let axis = if ax.abs() < ay.abs() && ax.abs() < az.abs() {
+                NVec::new([T::zero(), az, -ay])
+            } else if ay.abs() < az.abs() {
+                NVec::new([-az, T::zero(), ax])
+            } else {
+                NVec::new([ay, -ax, T::zero()])
+            };
+            return Quaternion::from_parts(T::zero(), axis).normalize();
+        }
+
+        // half way trick - (|a||b| + a.b, a x b) is the rotation by the angle between them, once normalised
+        Quaternion::new(lens + dot, ay * bz - az * by, az * bx - ax * bz, ax * by - ay * bx).normalize()
+    }
+
+    pub fn conjugate(&self) -> Self {
+        Quaternion::new(self.w, -self.x, -self.y, -self.z)
+    }
+
+    pub fn dot(&self, rhs: &Self) -> T {
+        self.w * rhs.w + self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
+    }
+
+    pub fn norm_squared(&self) -> T {
+        self.dot(self)
+    }
+
+    pub fn norm(&self) -> T {
+        self.norm_squared().sqrt()
+    }
+
+    // zero quaternion has no direction, so gives back the identity rather than NaNs
+    pub fn normalize(&self) -> Self {
+        let n = self.norm();
+        if n == T::zero() {
+            return Quaternion::identity();
+        }
+        self.scale(T::one() / n)
+    }
+
+    // None for the zero quaternion since it has no inverse
+    pub fn inverse(&self) -> Option<Self> {
+        let n2 = self.norm_squared();
+        if n2 == T::zero() {
+            return None;
+        }
+        Some(self.conjugate().scale(T::one() / n2))
+    }
+
+    pub fn scale(&self, s: T) -> Self {
+        Quaternion::new(self.w * s, self.x * s, self.y * s, self.z * s)
+    }
+
+    // applies the rotation to v via the sandwich product q v q*
+    // expanded out rather than doing two full Hamilton products - assumes q is a unit quaternion
+    pub fn rotate(&self, v: NVec<T, 3>) -> NVec<T, 3>
+    where
+        T: Numerical,
+    {
+        let [vx, vy, vz] = v.components;
+        let two = T::one() + T::one();
+
+        // t = 2 (q_v x v)
+        let tx = two * (self.y * vz - self.z * vy);
+        let ty = two * (self.z * vx - self.x * vz);
+        let tz = two * (self.x * vy - self.y * vx);
+
+        // v + w t + q_v x t
+        NVec::new([
+            vx + self.w * tx + (self.y * tz - self.z * ty),
+            vy + self.w * ty + (self.z * tx - self.x * tz),
+            vz + self.w * tz + (self.x * ty - self.y * tx),
+        ])
+    }
+
+    // normalised linear interpolation - cheap, and fine when the two are close together
+    pub fn nlerp(&self, other: &Self, t: T) -> Self {
+        let other = if self.dot(other) < T::zero() { -*other } else { *other }; //go the short way round
+
+        (*self * (T::one() - t) + other * t).normalize()
+    }
+
+    // spherical linear interpolation - constant angular speed from self (t = 0) to other (t = 1)
+    pub fn slerp(&self, other: &Self, t: T) -> Self {
+        let mut cos_theta = self.dot(other);
+        let mut other = *other;
+
+        if cos_theta < T::zero() {
+            other = -other;
+            cos_theta = -cos_theta;
+        }
+
+        // nearly parallel - sin(theta) heads to 0, so fall back to nlerp which is indistinguishable here
+        if cos_theta > T::one() - T::epsilon().sqrt() {
+            return self.nlerp(&other, t);
+        }
+
+        let theta = cos_theta.acos();
+        let sin_theta = theta.sin();
+        let a = ((T::one() - t) * theta).sin() / sin_theta;
+        let b = (t * theta).sin() / sin_theta;
+
+        *self * a + other * b
+    }
+
+    // row major 3x3 matrix performing the same rotation - assumes a unit quaternion
+    pub fn to_rotation_matrix(&self) -> [[T; 3]; 3] {
+        let Quaternion { w, x, y, z } = *self;
+        let one = T::one();
+        let two = one + one;
+
+        [
+            [one - two * (y * y + z * z), two * (x * y - w * z), two * (x * z + w * y)],
+            [two * (x * y + w * z), one - two * (x * x + z * z), two * (y * z - w * x)],
+            [two * (x * z - w * y), two * (y * z + w * x), one - two * (x * x + y * y)],
+        ]
+    }
+
+    // Shepperd's method - picks the biggest diagonal term to divide by so we never divide by something tiny
+    pub fn from_rotation_matrix(m: [[T; 3]; 3]) -> Self {
+        let one = T::one();
+        let two = one + one;
+        let four = two + two;
+        let trace = m[0][0] + m[1][1] + m[2][2];
+
+        let q = if trace > T::zero() {
+            let s = (trace + one).sqrt() * two; // s = 4w
+            Quaternion::new(s / four, (m[2][1] - m[1][2]) / s, (m[0][2] - m[2][0]) / s, (m[1][0] - m[0][1]) / s)
+        } else if m[0][0] > m[1][1] && m[0][0] > m[2][2] {
+            let s = (one + m[0][0] - m[1][1] - m[2][2]).sqrt() * two; // s = 4x
+            Quaternion::new((m[2][1] - m[1][2]) / s, s / four, (m[0][1] + m[1][0]) / s, (m[0][2] + m[2][0]) / s)
+        } else if m[1][1] > m[2][2] {
+            let s = (one + m[1][1] - m[0][0] - m[2][2]).sqrt() * two; // s = 4y
+            Quaternion::new((m[0][2] - m[2][0]) / s, (m[0][1] + m[1][0]) / s, s / four, (m[1][2] + m[2][1]) / s)
+        } else {
+            let s = (one + m[2][2] - m[0][0] - m[1][1]).sqrt() * two; // s = 4z
+            Quaternion::new((m[1][0] - m[0][1]) / s, (m[0][2] + m[2][0]) / s, (m[1][2] + m[2][1]) / s, s / four)
+        };
+
+        q.normalize()
+    }
+}
+
+
+
+// Operations //
+
+// Hamilton product - composes rotations, (a * b) rotates by b first then a
+impl<T: Float> Mul for Quaternion<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Quaternion::new(
+            self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+            self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+        )
+    }
+}
+
+// q * s scales every component
+impl<T: Float> Mul<T> for Quaternion<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        self.scale(rhs)
+    }
+}
+
+// q * v rotates v, same as q.rotate(v)
+impl<T: Float + Numerical> Mul<NVec<T, 3>> for Quaternion<T> {
+    type Output = NVec<T, 3>;
+
+    fn mul(self, rhs: NVec<T, 3>) -> NVec<T, 3> {
+        self.rotate(rhs)
+    }
+}
+
+impl<T: Float> Add for Quaternion<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Quaternion::new(self.w + rhs.w, self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Float> Sub for Quaternion<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Quaternion::new(self.w - rhs.w, self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+// -q is the same rotation as q, just the other way round the 4D sphere
+impl<T: Float> Neg for Quaternion<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Quaternion::new(-self.w, -self.x, -self.y, -self.z)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    fn close_q(a: Quaternion<f64>, b: Quaternion<f64>) -> bool {
+        (a - b).norm() < 1e-12 || (a + b).norm() < 1e-12 // q and -q are the same rotation
+    }
+
+    #[test]
+    fn rotation_test() {
+        println!("\n\nTesting for quaternion rotation\n\n");
+        let q = Quaternion::from_axis_angle(NVec::new([0.0, 0.0, 2.0]), FRAC_PI_2);
+        let v = NVec::new([1.0, 0.0, 0.0]);
+
+        assert_nvec_approx_eq!(q.rotate(v), NVec::new([0.0, 1.0, 0.0]), epsilon = 1e-12);
+        assert_nvec_approx_eq!(q * (q * v), NVec::new([-1.0, 0.0, 0.0]), epsilon = 1e-12);
+        assert_nvec_approx_eq!((q * q).rotate(v), NVec::new([-1.0, 0.0, 0.0]), epsilon = 1e-12);
+        assert_nvec_approx_eq!(q.inverse().unwrap().rotate(q.rotate(v)), v, epsilon = 1e-12);
+        assert!(close_q(q * q.conjugate(), Quaternion::identity()));
+        assert_eq!(Quaternion::new(0.0, 0.0, 0.0, 0.0).inverse(), None);
+
+        let (axis, angle) = q.to_axis_angle();
+        assert_nvec_approx_eq!(axis, NVec::new([0.0, 0.0, 1.0]), epsilon = 1e-12);
+        assert!((angle - FRAC_PI_2).abs() < 1e-12);
+
+        // rotating a vector and rotating by the equivalent matrix should agree
+        let r = Quaternion::from_axis_angle(NVec::new([1.0, -2.0, 0.5]), 1.234);
+        let m = r.to_rotation_matrix();
+        let w = NVec::new([0.3, 0.7, -1.1]);
+        let mw = NVec::new(m.map(|row| row[0] * w.components[0] + row[1] * w.components[1] + row[2] * w.components[2]));
+        assert_nvec_approx_eq!(r.rotate(w), mw, epsilon = 1e-12);
+        assert!(close_q(Quaternion::from_rotation_matrix(m), r));
+        assert!(close_q(Quaternion::from_rotation_matrix(Quaternion::from_axis_angle(NVec::new([1.0, 1.0, 0.0]), PI).to_rotation_matrix()), Quaternion::from_axis_angle(NVec::new([1.0, 1.0, 0.0]), PI)));
+    }
+
+    #[test]
+    fn construction_test() {
+        println!("\n\nTesting for quaternion construction\n\n");
+        let (roll, pitch, yaw) = (0.3, -0.7, 2.1);
+        let q = Quaternion::from_euler(roll, pitch, yaw);
+        let composed = Quaternion::from_axis_angle(NVec::new([0.0, 0.0, 1.0]), yaw)
+            * Quaternion::from_axis_angle(NVec::new([0.0, 1.0, 0.0]), pitch)
+            * Quaternion::from_axis_angle(NVec::new([1.0, 0.0, 0.0]), roll);
+        assert!(close_q(q, composed));
+
+        let (r, p, y) = q.to_euler();
+        assert!((r - roll).abs() < 1e-12 && (p - pitch).abs() < 1e-12 && (y - yaw).abs() < 1e-12);
+
+        let a = NVec::new([1.0, 2.0, 3.0]);
+        let b = NVec::new([-2.0, 0.5, 1.0]);
+        let q = Quaternion::from_two_vectors(a, b);
+        assert_nvec_approx_eq!(q.rotate(a) * (1.0 / a.mag()), b * (1.0 / b.mag()), epsilon = 1e-12);
+
+        let opposite = Quaternion::from_two_vectors(a, a * -1.0);
+        assert_nvec_approx_eq!(opposite.rotate(a), a * -1.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn interpolation_test() {
+        println!("\n\nTesting for quaternion interpolation\n\n");
+        let axis = NVec::new([0.0, 1.0, 0.0]);
+        let a = Quaternion::identity();
+        let b = Quaternion::from_axis_angle(axis, 2.0);
+
+        assert!(close_q(a.slerp(&b, 0.0), a));
+        assert!(close_q(a.slerp(&b, 1.0), b));
+        assert!(close_q(a.slerp(&b, 0.25), Quaternion::from_axis_angle(axis, 0.5)));
+        assert!(close_q(a.nlerp(&b, 0.5), Quaternion::from_axis_angle(axis, 1.0)));
+        assert!(close_q(a.slerp(&-b, 0.5), Quaternion::from_axis_angle(axis, 1.0))); //takes the short way
+    }
+}
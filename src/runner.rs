@@ -22,7 +22,7 @@ fn main(){
 
 
     let c = NVec::new([2.0, 0.2, -3.2]);
-    print!("{:?}\n", c.cross(a) * -1);
+    println!("{:?}", c.cross(a) * -1);
     print!("{:?}", a.cross(c));
 
     
@@ -0,0 +1,362 @@
+use super::*;
+use std::fmt;
+use std::iter::Sum;
+
+// Standard library trait implementations
+// These are what let an NVec slot in to ordinary Rust code - indexing, for loops, collecting, printing, hashing etc.
+// without ever needing to reach in to .components (see the Deref discussion at the bottom of lib.rs)
+// Eq/Hash/Ord are derived on the struct itself so they turn up automatically for integer components
+
+
+
+// Indexing //
+
+impl<T, const N: usize> Index<usize> for NVec<T, N> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        &self.components[i]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for NVec<T, N> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        &mut self.components[i]
+    }
+}
+
+impl<T, const N: usize> AsRef<[T]> for NVec<T, N> {
+    fn as_ref(&self) -> &[T] {
+        &self.components
+    }
+}
+
+impl<T, const N: usize> AsMut<[T]> for NVec<T, N> {
+    fn as_mut(&mut self) -> &mut [T] {
+        &mut self.components
+    }
+}
+
+
+
+// Iterating //
+
+impl<T, const N: usize> IntoIterator for NVec<T, N> {
+    type Item = T;
+    type IntoIter = std::array::IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.components.into_iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a NVec<T, N> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.components.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut NVec<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.components.iter_mut()
+    }
+}
+
+impl<T, const N: usize> NVec<T, N> {
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.components.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.components.iter_mut()
+    }
+
+    // TryFrom can't be implemented for every iterator (it would overlap with the blanket TryFrom<U> for T: From<U>), so this does the same job
+    // Fails unless the iterator gives exactly N items
+    // Stops reading after N + 1, so it's fine with endless iterators - but that also means found is N + 1 for anything too long
+    pub fn try_from_iter<I: IntoIterator<Item = T>>(iter: I) -> Result<Self, NVecError> {
+        let items: Vec<T> = iter.into_iter().take(N + 1).collect();
+        Self::try_from(items)
+    }
+}
+
+
+
+// Conversions //
+
+impl<T, const N: usize> From<[T; N]> for NVec<T, N> {
+    fn from(components: [T; N]) -> Self {
+        NVec { components }
+    }
+}
+
+impl<T, const N: usize> From<NVec<T, N>> for [T; N] {
+    fn from(v: NVec<T, N>) -> Self {
+        v.components
+    }
+}
+
+impl<T: Copy, const N: usize> TryFrom<&[T]> for NVec<T, N> {
+    type Error = NVecError;
+
+    fn try_from(slice: &[T]) -> Result<Self, Self::Error> {
+        let components: [T; N] = slice.try_into().map_err(|_| NVecError::DimensionMismatch { expected: N, found: slice.len() })?;
+        Ok(NVec { components })
+    }
+}
+
+impl<T, const N: usize> TryFrom<Vec<T>> for NVec<T, N> {
+    type Error = NVecError;
+
+    fn try_from(items: Vec<T>) -> Result<Self, Self::Error> {
+        let found = items.len();
+        let components: [T; N] = items.try_into().map_err(|_| NVecError::DimensionMismatch { expected: N, found })?;
+        Ok(NVec { components })
+    }
+}
+
+// tuples for the small sizes people actually write out by hand
+macro_rules! tuple_conversions {
+    ($(($n:literal, $($name:ident),+)), *) => {
+        $(
+            impl<T> From<($(tuple_conversions!(@ty T, $name),)+)> for NVec<T, $n> {
+                fn from(($($name,)+): ($(tuple_conversions!(@ty T, $name),)+)) -> Self {
+                    NVec { components: [$($name),+] }
+                }
+            }
+
+            impl<T> From<NVec<T, $n>> for ($(tuple_conversions!(@ty T, $name),)+) {
+                fn from(v: NVec<T, $n>) -> Self {
+                    let [$($name),+] = v.components;
+                    ($($name,)+)
+                }
+            }
+        )*
+    };
+    (@ty $t:ident, $name:ident) => { $t };
+}
+
+tuple_conversions!((1, x), (2, x, y), (3, x, y, z), (4, x, y, z, w));
+
+
+
+// Zero and summing //
+
+impl<T: Default, const N: usize> Default for NVec<T, N> {
+    fn default() -> Self {
+        NVec { components: core::array::from_fn(|_| T::default()) }
+    }
+}
+
+impl<T, const N: usize> num_traits::Zero for NVec<T, N>
+where
+    T: Numerical + num_traits::Zero + AsPrimitive<T> + Promote<T, Output = T>,
+{
+    fn zero() -> Self {
+        NVec { components: [T::zero(); N] }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.components.iter().all(|c| c.is_zero())
+    }
+}
+
+impl<T, const N: usize> Sum for NVec<T, N>
+where
+    T: Numerical + num_traits::Zero + AsPrimitive<T> + Promote<T, Output = T>,
+{
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(num_traits::Zero::zero(), |acc, v| acc + v)
+    }
+}
+
+impl<'a, T, const N: usize> Sum<&'a NVec<T, N>> for NVec<T, N>
+where
+    T: Numerical + num_traits::Zero + AsPrimitive<T> + Promote<T, Output = T>,
+{
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(num_traits::Zero::zero(), |acc, v| acc + *v)
+    }
+}
+
+
+
+// Ord and ComponentWise both have min/max/clamp, so for integer NVecs a.min(b) would be ambiguous
+// These inherent versions win, so a.min(b) is always component-wise like you'd expect for vectors
+// Use Ord::min(a, b) if you really want the lexicographically smaller vector
+impl<T: Numerical, const N: usize> NVec<T, N> {
+    pub fn min<U, V>(self, rhs: NVec<U, N>) -> NVec<V, N>
+    where
+        T: AsPrimitive<V> + Promote<U, Output = V>,
+        U: Numerical + AsPrimitive<V>,
+        V: Numerical + 'static,
+    {
+        ComponentWise::min(self, rhs)
+    }
+
+    pub fn max<U, V>(self, rhs: NVec<U, N>) -> NVec<V, N>
+    where
+        T: AsPrimitive<V> + Promote<U, Output = V>,
+        U: Numerical + AsPrimitive<V>,
+        V: Numerical + 'static,
+    {
+        ComponentWise::max(self, rhs)
+    }
+
+    pub fn clamp(self, lo: Self, hi: Self) -> Self {
+        ComponentWise::clamp(self, lo, hi)
+    }
+}
+
+
+
+// Display //
+
+// How to lay out an NVec as text - the default gives [1, 2, 3]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DisplayOptions<'a> {
+    pub precision: Option<usize>, //decimal places, None for the shortest form that reads back exactly
+    pub separator: &'a str,
+    pub open: &'a str,
+    pub close: &'a str,
+}
+
+impl Default for DisplayOptions<'_> {
+    fn default() -> Self {
+        DisplayOptions { precision: None, separator: ", ", open: "[", close: "]" }
+    }
+}
+
+// An NVec paired with the options to print it with, made by NVec::display_with
+pub struct DisplayWith<'a, T, const N: usize> {
+    vec: &'a NVec<T, N>,
+    options: DisplayOptions<'a>,
+}
+
+impl<T: fmt::Display, const N: usize> NVec<T, N> {
+    // e.g. println!("{}", v.display_with(DisplayOptions { separator: "\t", open: "", close: "", ..Default::default() }))
+    pub fn display_with<'a>(&'a self, options: DisplayOptions<'a>) -> DisplayWith<'a, T, N> {
+        DisplayWith { vec: self, options }
+    }
+}
+
+fn write_components<T: fmt::Display>(f: &mut fmt::Formatter<'_>, components: &[T], options: &DisplayOptions<'_>) -> fmt::Result {
+    write!(f, "{}", options.open)?;
+    for (i, c) in components.iter().enumerate() {
+        if i > 0 {
+            write!(f, "{}", options.separator)?;
+        }
+        match options.precision {
+            Some(p) => write!(f, "{:.*}", p, c)?,
+            None => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "{}", options.close)
+}
+
+// picks up the precision from the format string, so format!("{:.2}", v) works
+impl<T: fmt::Display, const N: usize> fmt::Display for NVec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let options = DisplayOptions { precision: f.precision(), ..Default::default() };
+        write_components(f, &self.components, &options)
+    }
+}
+
+impl<T: fmt::Display, const N: usize> fmt::Display for DisplayWith<'_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let options = DisplayOptions { precision: self.options.precision.or(f.precision()), ..self.options };
+        write_components(f, &self.vec.components, &options)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeSet, HashMap};
+
+    #[test]
+    fn access_test() {
+        println!("\n\nTesting for indexing and iterating\n\n");
+        let mut v = NVec::new([1, 2, 3]);
+        assert_eq!(v[1], 2);
+        v[2] = 10;
+        assert_eq!(v, NVec::new([1, 2, 10]));
+        assert_eq!(v.as_ref(), &[1, 2, 10]);
+
+        for c in &mut v {
+            *c *= 2;
+        }
+        assert_eq!((&v).into_iter().copied().collect::<Vec<_>>(), vec![2, 4, 20]);
+        assert_eq!(v.iter().sum::<i32>(), 26);
+        assert_eq!(v.into_iter().rev().collect::<Vec<_>>(), vec![20, 4, 2]);
+    }
+
+    #[test]
+    fn conversion_test() {
+        println!("\n\nTesting for conversions\n\n");
+        let v: NVec<f64, 3> = [1.0, 2.0, 3.0].into();
+        assert_eq!(<[f64; 3]>::from(v), [1.0, 2.0, 3.0]);
+        assert_eq!(NVec::from((1, 2)), NVec::new([1, 2]));
+        assert_eq!(<(u8, u8, u8, u8)>::from(NVec::new([1u8, 2, 3, 4])), (1, 2, 3, 4));
+
+        let slice: &[i64] = &[4, 5, 6];
+        assert_eq!(NVec::<i64, 3>::try_from(slice), Ok(NVec::new([4, 5, 6])));
+        assert_eq!(NVec::<i64, 2>::try_from(slice), Err(NVecError::DimensionMismatch { expected: 2, found: 3 }));
+        assert_eq!(NVec::<i32, 3>::try_from(vec![1, 2, 3]), Ok(NVec::new([1, 2, 3])));
+        assert_eq!(NVec::<i32, 4>::try_from_iter(0..4), Ok(NVec::new([0, 1, 2, 3])));
+        assert_eq!(NVec::<i32, 4>::try_from_iter(0..5), Err(NVecError::DimensionMismatch { expected: 4, found: 5 }));
+        assert_eq!(NVec::<i32, 4>::try_from_iter(0..100), Err(NVecError::DimensionMismatch { expected: 4, found: 5 }));
+        assert_eq!(NVec::<i32, 4>::try_from_iter(0..3), Err(NVecError::DimensionMismatch { expected: 4, found: 3 }));
+        assert_eq!(NVec::<u64, 3>::try_from_iter(std::iter::repeat(7)), Err(NVecError::DimensionMismatch { expected: 3, found: 4 })); //endless, still returns
+
+        assert_eq!(NVec::<u16, 3>::default(), NVec::new([0, 0, 0]));
+        assert!(<NVec<f32, 2> as num_traits::Zero>::zero() == NVec::new([0.0, 0.0]));
+
+        let total: NVec<f64, 2> = (1..=4).map(|i| NVec::new([i as f64, 1.0])).sum();
+        assert_eq!(total, NVec::new([10.0, 4.0]));
+        let list = [NVec::new([1, 2]), NVec::new([3, 4])];
+        assert_eq!(list.iter().sum::<NVec<i32, 2>>(), NVec::new([4, 6]));
+    }
+
+    #[test]
+    fn display_test() {
+        println!("\n\nTesting for display\n\n");
+        let v = NVec::new([1.5, -2.0, 0.1]);
+        assert_eq!(format!("{}", v), "[1.5, -2, 0.1]");
+        assert_eq!(format!("{:.2}", v), "[1.50, -2.00, 0.10]");
+        assert_eq!(format!("{}", NVec::new([1u8, 2, 3])), "[1, 2, 3]");
+
+        let tsv = DisplayOptions { separator: "\t", open: "", close: "", ..Default::default() };
+        assert_eq!(format!("{}", v.display_with(tsv)), "1.5\t-2\t0.1");
+        let fixed = DisplayOptions { precision: Some(1), separator: " ", open: "(", close: ")" };
+        assert_eq!(v.display_with(fixed).to_string(), "(1.5 -2.0 0.1)");
+    }
+
+    #[test]
+    fn hash_and_order_test() {
+        println!("\n\nTesting for hashing and ordering\n\n");
+        let mut counts = HashMap::new();
+        for v in [NVec::new([1, 2]), NVec::new([3, 4]), NVec::new([1, 2])] {
+            *counts.entry(v).or_insert(0) += 1;
+        }
+        assert_eq!(counts[&NVec::new([1, 2])], 2);
+
+        let sorted: BTreeSet<_> = [NVec::new([2, 0]), NVec::new([1, 5]), NVec::new([1, 3])].into_iter().collect();
+        assert_eq!(sorted.into_iter().collect::<Vec<_>>(), vec![NVec::new([1, 3]), NVec::new([1, 5]), NVec::new([2, 0])]);
+
+        // min/max are component-wise, Ord's versions are still there if you ask for them
+        let a = NVec::new([1, 9]);
+        let b = NVec::new([2, 0]);
+        assert_eq!(a.min(b), NVec::new([1, 0]));
+        assert_eq!(Ord::min(a, b), a);
+        assert_eq!(a.clamp(NVec::new([0, 0]), NVec::new([5, 5])), NVec::new([1, 5]));
+    }
+}
@@ -19,12 +19,32 @@ where
     }
 }
 
+// Same as -, but an Err on integer overflow (e.g. going below 0 for unsigned) or an inf/NaN float
+impl<T: Numerical, const N: usize> NVec<T, N> {
+    pub fn try_sub<U, V>(self, rhs: NVec<U, N>) -> Result<NVec<V, N>, NVecError>
+    where
+        T: AsPrimitive<V> + Promote<U, Output = V>,
+        U: Numerical + AsPrimitive<V>,
+        V: CheckedNumerical + 'static,
+    {
+        error::try_components(|i| self.components[i].as_().try_sub(rhs.components[i].as_(), i))
+    }
+}
+
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn try_sub_test() {
+        println!("\n\nTesting for checked subtraction\n\n");
+        assert_eq!(NVec::new([5u16, 7]).try_sub(NVec::new([1u16, 7])), Ok(NVec::new([4u16, 0])));
+        assert_eq!(NVec::new([5u16, 7]).try_sub(NVec::new([1u16, 8])), Err::<NVec<u16, 2>, _>(NVecError::Overflow { index: 1 }));
+        assert_eq!(NVec::new([-f64::MAX]).try_sub(NVec::new([f64::MAX])), Err::<NVec<f64, 1>, _>(NVecError::NonFinite { index: 0 }));
+    }
+
     #[test]
     fn same_type_test() {
         //declare all the operations, then call the tests to be assembled below
@@ -43,25 +63,27 @@ mod tests {
     fn casting_test(){
         println!("\n\nTesting for subtraction\n\n");
         let a: NVec<f64, 3> = NVec {
-            components: [1.01 as f64, -2.65 as f64, 3.4 as f64],
+            components: [1.01_f64, -2.65_f64, 3.4_f64],
         };
         let b: NVec<i32, 3> = NVec {
             components: [4, -5, 200],
         };
         println!("a, b\n");
-        assert_eq!(
+        assert_nvec_approx_eq!(
             a - b,
             NVec {
-                components: [1.01 - 4f64, -2.65 - -5f64, 3.4 - 200f64]
-            }
+                components: [-2.99f64, 2.35f64, -196.6f64]
+            },
+            relative = 1e-12
         );
     
         println!("b, a\n");
-        assert_eq!(
+        assert_nvec_approx_eq!(
             b - a,
             NVec {
-                components: [-1.01 + 4f64, 2.65 + -5f64, -3.4 + 200f64]
-            }
+                components: [2.99f64, -2.35f64, 196.6f64]
+            },
+            relative = 1e-12
         );
     }
 }
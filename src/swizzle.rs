@@ -0,0 +1,220 @@
+use super::*;
+
+// Swizzling and sub-vectors
+// Named accessors (x/y/z/w and r/g/b/a), every 2 and 3 letter xyzw swizzle, and a few 4 letter ones
+// Anything that reaches past the end of the NVec is a compile error rather than a panic, e.g. NVec<f64, 2>::z() won't build
+// Const generics can't be added together on stable, so subvec/split_at/concat get the output lengths from you or from type inference
+// and check them at compile time instead
+
+// asserts every index is in range when the method is instantiated, so it fails the build rather than at runtime
+macro_rules! check_indices {
+    ($n:expr, $($i:expr),+) => {
+        const { $(assert!($i < $n, "Swizzle index out of range for this NVec");)+ }
+    };
+}
+
+macro_rules! accessors {
+    ($(($index:literal, $get:ident, $get_mut:ident, $colour:ident)), *) => {
+        impl<T: Copy, const N: usize> NVec<T, N> {
+            $(
+                pub fn $get(&self) -> T {
+                    check_indices!(N, $index);
+                    self.components[$index]
+                }
+
+                pub fn $get_mut(&mut self) -> &mut T {
+                    check_indices!(N, $index);
+                    &mut self.components[$index]
+                }
+
+                pub fn $colour(&self) -> T {
+                    self.$get()
+                }
+            )*
+        }
+    };
+}
+
+accessors!((0, x, x_mut, r), (1, y, y_mut, g), (2, z, z_mut, b), (3, w, w_mut, a));
+
+macro_rules! swizzles {
+    ($m:literal; $($name:ident [$($i:literal),+]), *) => {
+        impl<T: Copy, const N: usize> NVec<T, N> {
+            $(
+                pub fn $name(&self) -> NVec<T, $m> {
+                    check_indices!(N, $($i),+);
+                    NVec { components: [$(self.components[$i]),+] }
+                }
+            )*
+        }
+    };
+}
+
+swizzles!(2;
+    xx [0, 0], xy [0, 1], xz [0, 2], xw [0, 3], yx [1, 0], yy [1, 1],
+    yz [1, 2], yw [1, 3], zx [2, 0], zy [2, 1], zz [2, 2], zw [2, 3],
+    wx [3, 0], wy [3, 1], wz [3, 2], ww [3, 3]
+);
+
+swizzles!(3;
+    xxx [0, 0, 0], xxy [0, 0, 1], xxz [0, 0, 2], xxw [0, 0, 3], xyx [0, 1, 0], xyy [0, 1, 1],
+    xyz [0, 1, 2], xyw [0, 1, 3], xzx [0, 2, 0], xzy [0, 2, 1], xzz [0, 2, 2], xzw [0, 2, 3],
+    xwx [0, 3, 0], xwy [0, 3, 1], xwz [0, 3, 2], xww [0, 3, 3], yxx [1, 0, 0], yxy [1, 0, 1],
+    yxz [1, 0, 2], yxw [1, 0, 3], yyx [1, 1, 0], yyy [1, 1, 1], yyz [1, 1, 2], yyw [1, 1, 3],
+    yzx [1, 2, 0], yzy [1, 2, 1], yzz [1, 2, 2], yzw [1, 2, 3], ywx [1, 3, 0], ywy [1, 3, 1],
+    ywz [1, 3, 2], yww [1, 3, 3], zxx [2, 0, 0], zxy [2, 0, 1], zxz [2, 0, 2], zxw [2, 0, 3],
+    zyx [2, 1, 0], zyy [2, 1, 1], zyz [2, 1, 2], zyw [2, 1, 3], zzx [2, 2, 0], zzy [2, 2, 1],
+    zzz [2, 2, 2], zzw [2, 2, 3], zwx [2, 3, 0], zwy [2, 3, 1], zwz [2, 3, 2], zww [2, 3, 3],
+    wxx [3, 0, 0], wxy [3, 0, 1], wxz [3, 0, 2], wxw [3, 0, 3], wyx [3, 1, 0], wyy [3, 1, 1],
+    wyz [3, 1, 2], wyw [3, 1, 3], wzx [3, 2, 0], wzy [3, 2, 1], wzz [3, 2, 2], wzw [3, 2, 3],
+    wwx [3, 3, 0], wwy [3, 3, 1], wwz [3, 3, 2], www [3, 3, 3]
+);
+
+// the 4 letter ones people actually use, plus colour orderings
+swizzles!(3; rgb [0, 1, 2], bgr [2, 1, 0]);
+
+swizzles!(4;
+    xyzw [0, 1, 2, 3], wxyz [3, 0, 1, 2], wzyx [3, 2, 1, 0], zyxw [2, 1, 0, 3],
+    rgba [0, 1, 2, 3], bgra [2, 1, 0, 3], argb [3, 0, 1, 2], abgr [3, 2, 1, 0]
+);
+
+// padding with a 0 or 1 on the end, for going in to homogeneous coordinates
+impl<T: Copy + num_traits::Zero + num_traits::One, const N: usize> NVec<T, N> {
+    pub fn xy0(&self) -> NVec<T, 3> {
+        check_indices!(N, 1);
+        NVec { components: [self.components[0], self.components[1], T::zero()] }
+    }
+
+    pub fn xy1(&self) -> NVec<T, 3> {
+        check_indices!(N, 1);
+        NVec { components: [self.components[0], self.components[1], T::one()] }
+    }
+
+    pub fn xyz0(&self) -> NVec<T, 4> {
+        check_indices!(N, 2);
+        NVec { components: [self.components[0], self.components[1], self.components[2], T::zero()] }
+    }
+
+    pub fn xyz1(&self) -> NVec<T, 4> {
+        check_indices!(N, 2);
+        NVec { components: [self.components[0], self.components[1], self.components[2], T::one()] }
+    }
+}
+
+
+
+// Generic swizzles and sub-vectors //
+
+impl<T: Copy, const N: usize> NVec<T, N> {
+    // any pattern of indices, e.g. v.swizzle([3, 3, 0, 1, 2]) - panics if an index is out of range
+    // the swizzle2/3/4 versions below check at compile time instead
+    pub fn swizzle<const M: usize>(&self, indices: [usize; M]) -> NVec<T, M> {
+        for i in indices {
+            assert!(i < N, "Swizzle index {} out of range for an NVec of length {}", i, N);
+        }
+        NVec { components: indices.map(|i| self.components[i]) }
+    }
+
+    /// Picks two components by index, checked at compile time:
+    ///
+    /// ```compile_fail
+    /// use nvecslib::*;
+    /// let v = NVec::new([1.0, 2.0]);
+    /// let w = v.swizzle2::<0, 2>();
+    /// ```
+    pub fn swizzle2<const I: usize, const J: usize>(&self) -> NVec<T, 2> {
+        check_indices!(N, I, J);
+        NVec { components: [self.components[I], self.components[J]] }
+    }
+
+    pub fn swizzle3<const I: usize, const J: usize, const K: usize>(&self) -> NVec<T, 3> {
+        check_indices!(N, I, J, K);
+        NVec { components: [self.components[I], self.components[J], self.components[K]] }
+    }
+
+    pub fn swizzle4<const I: usize, const J: usize, const K: usize, const L: usize>(&self) -> NVec<T, 4> {
+        check_indices!(N, I, J, K, L);
+        NVec { components: [self.components[I], self.components[J], self.components[K], self.components[L]] }
+    }
+
+    // LEN components starting at START, e.g. v.subvec::<1, 3>() is [v1, v2, v3]
+    // not called sub, since an inherent sub would hide Sub::sub and break a.sub(b)
+    pub fn subvec<const START: usize, const LEN: usize>(&self) -> NVec<T, LEN> {
+        const { assert!(START + LEN <= N, "Sub-vector runs past the end of the NVec") };
+        NVec { components: core::array::from_fn(|i| self.components[START + i]) }
+    }
+
+    // the first K components and the remaining M, where K + M has to be N
+    // e.g. let (head, tail): (NVec<f64, 1>, NVec<f64, 3>) = v.split_at(); or v.split_at::<1, _>()
+    pub fn split_at<const K: usize, const M: usize>(&self) -> (NVec<T, K>, NVec<T, M>) {
+        const { assert!(K + M == N, "split_at lengths have to add up to the length of the NVec") };
+        (
+            NVec { components: core::array::from_fn(|i| self.components[i]) },
+            NVec { components: core::array::from_fn(|i| self.components[K + i]) },
+        )
+    }
+
+    // self followed by other, where M has to be N + K
+    pub fn concat<const K: usize, const M: usize>(&self, other: NVec<T, K>) -> NVec<T, M> {
+        const { assert!(N + K == M, "concat output length has to be the sum of the input lengths") };
+        NVec { components: core::array::from_fn(|i| if i < N { self.components[i] } else { other.components[i - N] }) }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named_test() {
+        println!("\n\nTesting for named swizzles\n\n");
+        let mut v = NVec::new([1, 2, 3, 4]);
+        assert_eq!((v.x(), v.y(), v.z(), v.w()), (1, 2, 3, 4));
+        assert_eq!((v.r(), v.g(), v.b(), v.a()), (1, 2, 3, 4));
+        *v.z_mut() = 30;
+        assert_eq!(v, NVec::new([1, 2, 30, 4]));
+
+        assert_eq!(v.xy(), NVec::new([1, 2]));
+        assert_eq!(v.zyx(), NVec::new([30, 2, 1]));
+        assert_eq!(v.wwx(), NVec::new([4, 4, 1]));
+        assert_eq!(v.bgra(), NVec::new([30, 2, 1, 4]));
+
+        let p = NVec::new([1.5, -2.0, 0.5]);
+        assert_eq!(p.xyz0(), NVec::new([1.5, -2.0, 0.5, 0.0]));
+        assert_eq!(p.xyz1(), NVec::new([1.5, -2.0, 0.5, 1.0]));
+        assert_eq!(p.xy1(), NVec::new([1.5, -2.0, 1.0]));
+        assert_eq!(p.rgb(), p);
+    }
+
+    #[test]
+    fn generic_test() {
+        println!("\n\nTesting for generic swizzles and sub-vectors\n\n");
+        let v = NVec::new([10, 11, 12, 13, 14, 15]);
+        assert_eq!(v.swizzle([5, 0, 5]), NVec::new([15, 10, 15]));
+        assert_eq!(v.swizzle2::<4, 1>(), NVec::new([14, 11]));
+        assert_eq!(v.swizzle4::<0, 0, 5, 2>(), NVec::new([10, 10, 15, 12]));
+
+        assert_eq!(v.subvec::<1, 3>(), NVec::new([11, 12, 13]));
+        assert_eq!(v.subvec::<6, 0>(), NVec { components: [] });
+
+        // the trait method is still reachable with method call syntax
+        use std::ops::Sub;
+        assert_eq!(v.sub(v.subvec::<0, 6>()), NVec::new([0; 6]));
+
+        let (head, tail) = v.split_at::<2, _>();
+        assert_eq!(head, NVec::new([10, 11]));
+        assert_eq!(tail, NVec::new([12, 13, 14, 15]));
+        assert_eq!(head.concat::<4, 6>(tail), v);
+
+        let joined: NVec<i32, 5> = v.xy().concat(v.zyx());
+        assert_eq!(joined, NVec::new([10, 11, 12, 11, 10]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn runtime_swizzle_out_of_range_test() {
+        NVec::new([1, 2, 3]).swizzle([0, 3]);
+    }
+}
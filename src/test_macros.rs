@@ -1,5 +1,3 @@
-#[cfg(test)]
-
 // Each macro needs #[macro_export] so it can be used by the tests in each implementation file
 
 //Need to generate tests for operations betwee NVecs with a different type T
@@ -9,6 +7,7 @@
 // op1 is the operation to appply to the NVecs themselves
 // op2 is the operation to apply to the components of each matched elementwise to get the same effect
 
+#[cfg(test)]
 #[macro_export]
 macro_rules! generate_same_type_tests {
     ($op1:ident, $op2:ident, $val1:expr, $val2:expr, $val3:expr, $($type:ty), *) => {
@@ -0,0 +1,286 @@
+use super::*;
+use num_traits::Float;
+
+// Unit length vectors
+// A UnitVec can only be made by normalising an NVec (which fails for the zero vector) or by promising it's already unit length
+// So anything taking a UnitVec - plane normals, rotation axes, reflections - can rely on it without checking again
+// Ops that might let rounding errors creep in give back a renormalised UnitVec
+// Derefs to the NVec inside for reading, but there's deliberately no DerefMut since that would let you break the invariant
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct UnitVec<T, const N: usize>(NVec<T, N>);
+
+impl<T, const N: usize> UnitVec<T, N>
+where
+    T: Float + Numerical + Default + AsPrimitive<T> + Promote<T, Output = T>,
+{
+    // None for the zero vector (or anything with a NaN/infinite length) since it has no direction
+    pub fn new(v: NVec<T, N>) -> Option<Self> {
+        Self::try_new(v).ok()
+    }
+
+    // new but saying why - ZeroLength for the zero vector, NonFinite at the component to blame for a NaN/infinite length
+    // (the first one that isn't finite, or the biggest if they all are and squaring them overflowed)
+    pub fn try_new(v: NVec<T, N>) -> Result<Self, NVecError> {
+        let len = Self::length(&v);
+        if len == T::zero() {
+            return Err(NVecError::ZeroLength);
+        }
+        if !len.is_finite() {
+            let index = v.iter().position(|c| !c.is_finite()).unwrap_or_else(|| {
+                (0..N).fold(0, |biggest, i| if v.components[i].abs() > v.components[biggest].abs() { i } else { biggest })
+            });
+            return Err(NVecError::NonFinite { index });
+        }
+        Ok(UnitVec(v * (T::one() / len)))
+    }
+
+    // skips the normalisation - it's on you to make sure v really is unit length
+    pub fn new_unchecked(v: NVec<T, N>) -> Self {
+        UnitVec(v)
+    }
+
+    // the i-th basis vector
+    pub fn basis(i: usize) -> Self {
+        assert!(i < N, "Basis vector index out of range for this dimension");
+        UnitVec(NVec::new(core::array::from_fn(|j| if i == j { T::one() } else { T::zero() })))
+    }
+
+    pub fn into_inner(self) -> NVec<T, N> {
+        self.0
+    }
+
+    // pulls the length back to 1 if it has drifted
+    pub fn renormalize(self) -> Self {
+        Self::new(self.0).unwrap_or(self)
+    }
+
+    // v reflected in the plane with this as its normal: v - 2 (v.n) n
+    pub fn reflect(&self, v: NVec<T, N>) -> NVec<T, N> {
+        let two = T::one() + T::one();
+        v - self.0 * (two * (v * self.0))
+    }
+
+    // the part of v along this direction
+    pub fn project(&self, v: NVec<T, N>) -> NVec<T, N> {
+        self.0 * (v * self.0)
+    }
+
+    // the angle to another direction in radians - clamped since rounding can push the dot just past 1
+    pub fn angle_to(&self, other: &Self) -> T {
+        (self.0 * other.0).max(-T::one()).min(T::one()).acos()
+    }
+
+    // spherical interpolation between two directions, the result is always unit length
+    pub fn slerp(&self, other: &Self, t: T) -> Self {
+        let theta = self.angle_to(other);
+        let sin_theta = theta.sin();
+
+        if sin_theta.abs() < T::epsilon().sqrt() {
+            if self.0 * other.0 > T::zero() {
+                // parallel - a straight line between them is as good as the arc
+                return UnitVec(self.0 * (T::one() - t) + other.0 * t).renormalize();
+            }
+            // antiparallel - every great circle through both works, so turn through any perpendicular direction
+            // (there isn't one in 1D, there it can only jump)
+            return match self.perpendicular() {
+                Some(p) => UnitVec(self.0 * (t * theta).cos() + p.0 * (t * theta).sin()).renormalize(),
+                None => if t < T::one() { *self } else { *other },
+            };
+        }
+
+        let a = ((T::one() - t) * theta).sin() / sin_theta;
+        let b = (t * theta).sin() / sin_theta;
+        UnitVec(self.0 * a + other.0 * b).renormalize()
+    }
+
+    // some direction at right angles to this one - the basis vector it's least aligned with, minus the part along self
+    // None in 1D (or less) where there's no room for one
+    fn perpendicular(&self) -> Option<Self> {
+        if N < 2 {
+            return None;
+        }
+        let k = (0..N).min_by(|&i, &j| {
+            let (a, b) = (self.0.components[i].abs(), self.0.components[j].abs());
+            a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+        })?;
+        let e = Self::basis(k).0;
+        Self::new(e - self.0 * self.0.components[k])
+    }
+
+    // same length as the NVec magnitude would give, but in T rather than f64 so it works for f32 too
+    fn length(v: &NVec<T, N>) -> T {
+        (*v * *v).sqrt()
+    }
+}
+
+impl<T> UnitVec<T, 3>
+where
+    T: Float + Numerical + Default + AsPrimitive<T> + Promote<T, Output = T>,
+{
+    // rotations keep length in theory, but not quite in floating point
+    pub fn rotate(&self, q: &Quaternion<T>) -> Self {
+        UnitVec(q.rotate(self.0)).renormalize()
+    }
+
+    // the unit normal of the plane spanned by self and other - None if they're parallel
+    pub fn cross_normalized(&self, other: &Self) -> Option<Self> {
+        self.try_cross_normalized(other).ok()
+    }
+
+    // Err(ZeroLength) if they're parallel
+    pub fn try_cross_normalized(&self, other: &Self) -> Result<Self, NVecError> {
+        UnitVec::try_new(self.0.cross(other.0))
+    }
+}
+
+impl<T, const N: usize> NVec<T, N>
+where
+    T: Float + Numerical + Default + AsPrimitive<T> + Promote<T, Output = T>,
+{
+    // the same as UnitVec::try_new(v), for when it reads better as a method
+    pub fn try_normalize(self) -> Result<UnitVec<T, N>, NVecError> {
+        UnitVec::try_new(self)
+    }
+}
+
+impl<T, const N: usize> Deref for UnitVec<T, N> {
+    type Target = NVec<T, N>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, const N: usize> From<UnitVec<T, N>> for NVec<T, N> {
+    fn from(u: UnitVec<T, N>) -> Self {
+        u.0
+    }
+}
+
+// the whole point - no need to work it out
+impl<T: Float, const N: usize> Magnitude for &UnitVec<T, N> {
+    type Output = T;
+
+    fn magnitude(self) -> Self::Output {
+        T::one()
+    }
+
+    fn mag(self) -> Self::Output {
+        T::one()
+    }
+}
+
+
+
+// Operations //
+
+// flipping the direction is exact so stays unit length
+impl<T: Float + Numerical, const N: usize> Neg for UnitVec<T, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        UnitVec(NVec::new(self.0.components.map(|c| -c)))
+    }
+}
+
+// scaling gives a plain NVec since it's no longer unit length
+impl<T, const N: usize> Mul<T> for UnitVec<T, N>
+where
+    T: Float + Numerical + Default + AsPrimitive<T> + Promote<T, Output = T>,
+{
+    type Output = NVec<T, N>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        self.0 * rhs
+    }
+}
+
+// dot product
+impl<T, const N: usize> Mul for UnitVec<T, N>
+where
+    T: Float + Numerical + Default + AsPrimitive<T> + Promote<T, Output = T>,
+{
+    type Output = T;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.0 * rhs.0
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn construction_test() {
+        println!("\n\nTesting for unit vector construction\n\n");
+        let u = UnitVec::new(NVec::new([3.0, 0.0, 4.0])).unwrap();
+        assert_nvec_approx_eq!(*u, NVec::new([0.6, 0.0, 0.8]), epsilon = 1e-12);
+        assert!((u.components[2] - 0.8).abs() < 1e-12); //reading through the deref
+        assert_eq!(u.mag(), 1.0);
+        assert_eq!(UnitVec::new(NVec::new([0.0f64, 0.0, 0.0])), None);
+        assert_eq!(UnitVec::new(NVec::new([f64::NAN, 0.0, 0.0])), None);
+        assert_eq!(UnitVec::try_new(NVec::new([0.0f64, 0.0, 0.0])), Err(NVecError::ZeroLength));
+        assert_eq!(NVec::new([1.0, f64::INFINITY, 0.0]).try_normalize(), Err(NVecError::NonFinite { index: 1 }));
+        assert_eq!(NVec::new([1.0, -1e300, 1e200]).try_normalize(), Err(NVecError::NonFinite { index: 1 })); //length overflows
+        assert_eq!(NVec::new([0.0f32, 2.0]).try_normalize().map(UnitVec::into_inner), Ok(NVec::new([0.0, 1.0])));
+        assert_eq!(UnitVec::<f32, 2>::basis(1).into_inner(), NVec::new([0.0, 1.0]));
+
+        let drifted = UnitVec::new_unchecked(NVec::new([1.0000001, 0.0, 0.0]));
+        assert_eq!(drifted.renormalize().into_inner(), NVec::new([1.0, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn ops_test() {
+        println!("\n\nTesting for unit vector ops\n\n");
+        let n = UnitVec::<f64, 3>::basis(2);
+        let v = NVec::new([1.0, 2.0, 3.0]);
+
+        assert_eq!(n.reflect(v), NVec::new([1.0, 2.0, -3.0]));
+        assert_eq!(n.project(v), NVec::new([0.0, 0.0, 3.0]));
+        assert_eq!((-n).into_inner(), NVec::new([0.0, 0.0, -1.0]));
+        assert_eq!(n * 2.0, NVec::new([0.0, 0.0, 2.0]));
+        assert_eq!(n * n, 1.0);
+
+        let x = UnitVec::<f64, 3>::basis(0);
+        let y = UnitVec::<f64, 3>::basis(1);
+        assert!((x.angle_to(&y) - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+        assert_eq!(x.cross_normalized(&y), Some(n));
+        assert_eq!(x.cross_normalized(&x), None);
+        assert_eq!(x.try_cross_normalized(&-x), Err(NVecError::ZeroLength));
+        assert_eq!(x.try_cross_normalized(&y), Ok(n));
+
+        let s = 0.5f64.sqrt();
+        assert_nvec_approx_eq!(*x.slerp(&y, 0.5), NVec::new([s, s, 0.0]), epsilon = 1e-12);
+
+        // antiparallel, the path goes round through some perpendicular direction rather than jumping at the end
+        let minus_x = -x;
+        let mid = x.slerp(&minus_x, 0.5);
+        assert!((Magnitude::mag(&*mid) - 1.0).abs() < 1e-12);
+        assert!((*mid * *x).abs() < 1e-12);
+        assert!((*mid * *minus_x).abs() < 1e-12);
+        assert_nvec_approx_eq!(*x.slerp(&minus_x, 1.0), *minus_x, epsilon = 1e-12);
+        let mut last = *x;
+        for i in 1..=100 {
+            let step = *x.slerp(&minus_x, i as f64 / 100.0);
+            assert!(Magnitude::mag(&(step - last)) < 0.05); //no jumps along the way
+            last = step;
+        }
+
+        let d = UnitVec::<f64, 3>::new(NVec::new([1.0, 2.0, -2.0])).unwrap();
+        let mid = d.slerp(&-d, 0.5);
+        assert!((Magnitude::mag(&*mid) - 1.0).abs() < 1e-12);
+        assert!((*mid * *d).abs() < 1e-12);
+        assert_nvec_approx_eq!(*x.slerp(&x, 0.3), *x, epsilon = 1e-12);
+
+        let q = Quaternion::from_axis_angle(*n, std::f64::consts::FRAC_PI_2);
+        let mut r = x;
+        for _ in 0..1000 {
+            r = r.rotate(&q);
+        }
+        assert!((Magnitude::mag(&*r) - 1.0).abs() < 1e-15); //still unit length after lots of rotations
+    }
+}
@@ -0,0 +1,572 @@
+use super::*;
+use std::marker::PhantomData;
+
+// Units of measure with dimensional analysis done by the type checker
+// A Quantity<T, D> is a number T with a physical dimension D
+// D records the power of each of the 7 SI base dimensions (length, mass, time, current, temperature, amount, luminosity)
+// Multiplying quantities adds the powers, dividing subtracts them, adding or subtracting needs them to match exactly
+// So NVec<Meters<f64>, 3> / Seconds<f64> is an NVec<MetersPerSecond<f64>, 3>, and adding that to a position won't compile
+// Everything is PhantomData, so there's no runtime cost over the bare numbers
+//
+// Quantities can't be Numerical (m * m isn't m), so the NVec ops for them are all implemented here separately
+
+
+
+// Type level integers //
+// Const generic arithmetic isn't stable, so the powers are counted out Peano style instead:
+// Z is zero, S<X> is X + 1, P<X> is X - 1
+// Only ever built in normal form (no S<P<..>>), which the ops below keep to
+
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Z;
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct S<X>(PhantomData<X>);
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct P<X>(PhantomData<X>);
+
+pub trait Integer {
+    const VALUE: i32;
+}
+
+impl Integer for Z {
+    const VALUE: i32 = 0;
+}
+
+impl<X: Integer> Integer for S<X> {
+    const VALUE: i32 = X::VALUE + 1;
+}
+
+impl<X: Integer> Integer for P<X> {
+    const VALUE: i32 = X::VALUE - 1;
+}
+
+// +1, cancelling a P if there is one
+pub trait Succ {
+    type Output;
+}
+
+impl Succ for Z {
+    type Output = S<Z>;
+}
+
+impl<X> Succ for S<X> {
+    type Output = S<S<X>>;
+}
+
+impl<X> Succ for P<X> {
+    type Output = X;
+}
+
+// -1, cancelling an S if there is one
+pub trait Pred {
+    type Output;
+}
+
+impl Pred for Z {
+    type Output = P<Z>;
+}
+
+impl<X> Pred for S<X> {
+    type Output = X;
+}
+
+impl<X> Pred for P<X> {
+    type Output = P<P<X>>;
+}
+
+pub trait Plus<Rhs> {
+    type Output;
+}
+
+// 0 + r = r
+impl<R> Plus<R> for Z {
+    type Output = R;
+}
+
+// (x + 1) + r = (x + r) + 1
+impl<X: Plus<R>, R> Plus<R> for S<X>
+where
+    <X as Plus<R>>::Output: Succ,
+{
+    type Output = <<X as Plus<R>>::Output as Succ>::Output;
+}
+
+// (x - 1) + r = (x + r) - 1
+impl<X: Plus<R>, R> Plus<R> for P<X>
+where
+    <X as Plus<R>>::Output: Pred,
+{
+    type Output = <<X as Plus<R>>::Output as Pred>::Output;
+}
+
+pub trait Negate {
+    type Output;
+}
+
+impl Negate for Z {
+    type Output = Z;
+}
+
+impl<X: Negate> Negate for S<X> {
+    type Output = P<<X as Negate>::Output>;
+}
+
+impl<X: Negate> Negate for P<X> {
+    type Output = S<<X as Negate>::Output>;
+}
+
+// x - r = x + (-r)
+pub trait Minus<Rhs> {
+    type Output;
+}
+
+impl<X, R: Negate> Minus<R> for X
+where
+    X: Plus<<R as Negate>::Output>,
+{
+    type Output = <X as Plus<<R as Negate>::Output>>::Output;
+}
+
+pub type N3 = P<P<P<Z>>>;
+pub type N2 = P<P<Z>>;
+pub type N1 = P<Z>;
+pub type P1 = S<Z>;
+pub type P2 = S<S<Z>>;
+pub type P3 = S<S<S<Z>>>;
+
+
+
+// Dimensions //
+
+// powers of length, mass, time, current, temperature, amount and luminosity
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Dim<L, M, Ti, I, Th, N, J>(PhantomData<(L, M, Ti, I, Th, N, J)>);
+
+pub trait DimMul<Rhs> {
+    type Output;
+}
+
+pub trait DimDiv<Rhs> {
+    type Output;
+}
+
+impl<L1, M1, Ti1, I1, Th1, N1_, J1, L2, M2, Ti2, I2, Th2, N2_, J2> DimMul<Dim<L2, M2, Ti2, I2, Th2, N2_, J2>> for Dim<L1, M1, Ti1, I1, Th1, N1_, J1>
+where
+    L1: Plus<L2>,
+    M1: Plus<M2>,
+    Ti1: Plus<Ti2>,
+    I1: Plus<I2>,
+    Th1: Plus<Th2>,
+    N1_: Plus<N2_>,
+    J1: Plus<J2>,
+{
+    type Output = Dim<L1::Output, M1::Output, Ti1::Output, I1::Output, Th1::Output, N1_::Output, J1::Output>;
+}
+
+impl<L1, M1, Ti1, I1, Th1, N1_, J1, L2, M2, Ti2, I2, Th2, N2_, J2> DimDiv<Dim<L2, M2, Ti2, I2, Th2, N2_, J2>> for Dim<L1, M1, Ti1, I1, Th1, N1_, J1>
+where
+    L1: Minus<L2>,
+    M1: Minus<M2>,
+    Ti1: Minus<Ti2>,
+    I1: Minus<I2>,
+    Th1: Minus<Th2>,
+    N1_: Minus<N2_>,
+    J1: Minus<J2>,
+{
+    type Output = Dim<L1::Output, M1::Output, Ti1::Output, I1::Output, Th1::Output, N1_::Output, J1::Output>;
+}
+
+// the unit written out in SI symbols, e.g. "m s^-1" - empty for dimensionless
+pub trait Dimension {
+    fn symbol() -> String;
+}
+
+impl<L: Integer, M: Integer, Ti: Integer, I: Integer, Th: Integer, N: Integer, J: Integer> Dimension for Dim<L, M, Ti, I, Th, N, J> {
+    fn symbol() -> String {
+        [("m", L::VALUE), ("kg", M::VALUE), ("s", Ti::VALUE), ("A", I::VALUE), ("K", Th::VALUE), ("mol", N::VALUE), ("cd", J::VALUE)]
+            .iter()
+            .filter(|(_, p)| *p != 0)
+            .map(|(u, p)| if *p == 1 { u.to_string() } else { format!("{}^{}", u, p) })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+pub type Dimensionless = Dim<Z, Z, Z, Z, Z, Z, Z>;
+pub type Length = Dim<P1, Z, Z, Z, Z, Z, Z>;
+pub type Mass = Dim<Z, P1, Z, Z, Z, Z, Z>;
+pub type Time = Dim<Z, Z, P1, Z, Z, Z, Z>;
+pub type Current = Dim<Z, Z, Z, P1, Z, Z, Z>;
+pub type Temperature = Dim<Z, Z, Z, Z, P1, Z, Z>;
+pub type Amount = Dim<Z, Z, Z, Z, Z, P1, Z>;
+pub type Luminosity = Dim<Z, Z, Z, Z, Z, Z, P1>;
+pub type Area = Dim<P2, Z, Z, Z, Z, Z, Z>;
+pub type Volume = Dim<P3, Z, Z, Z, Z, Z, Z>;
+pub type Velocity = Dim<P1, Z, N1, Z, Z, Z, Z>;
+pub type Acceleration = Dim<P1, Z, N2, Z, Z, Z, Z>;
+pub type Force = Dim<P1, P1, N2, Z, Z, Z, Z>;
+pub type Energy = Dim<P2, P1, N2, Z, Z, Z, Z>;
+
+
+
+// Quantities //
+
+/// A number with a physical unit.
+///
+/// Mixing units is rejected at compile time:
+///
+/// ```compile_fail
+/// use nvecslib::*;
+/// use nvecslib::units::*;
+/// let position: NVec<Meters<f64>, 3> = NVec::new_with_units([1.0, 2.0, 3.0]);
+/// let velocity: NVec<MetersPerSecond<f64>, 3> = NVec::new_with_units([1.0, 2.0, 3.0]);
+/// let wrong = position + velocity;
+/// ```
+pub struct Quantity<T, D> {
+    pub value: T,
+    unit: PhantomData<D>,
+}
+
+// Implemented by hand since derive would demand the same of D, which is never stored
+impl<T: Copy, D> Copy for Quantity<T, D> {}
+
+impl<T: Clone, D> Clone for Quantity<T, D> {
+    fn clone(&self) -> Self {
+        Quantity::new(self.value.clone())
+    }
+}
+
+impl<T: PartialEq, D> PartialEq for Quantity<T, D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: PartialOrd, D> PartialOrd for Quantity<T, D> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<T: Default, D> Default for Quantity<T, D> {
+    fn default() -> Self {
+        Quantity::new(T::default())
+    }
+}
+
+pub type Meters<T> = Quantity<T, Length>;
+pub type Kilograms<T> = Quantity<T, Mass>;
+pub type Seconds<T> = Quantity<T, Time>;
+pub type Amperes<T> = Quantity<T, Current>;
+pub type Kelvin<T> = Quantity<T, Temperature>;
+pub type Moles<T> = Quantity<T, Amount>;
+pub type Candelas<T> = Quantity<T, Luminosity>;
+pub type MetersPerSecond<T> = Quantity<T, Velocity>;
+pub type MetersPerSecondSquared<T> = Quantity<T, Acceleration>;
+pub type Newtons<T> = Quantity<T, Force>;
+pub type Joules<T> = Quantity<T, Energy>;
+
+impl<T, D> Quantity<T, D> {
+    pub fn new(value: T) -> Self {
+        Quantity { value, unit: PhantomData }
+    }
+}
+
+impl<T: std::fmt::Debug, D: Dimension> std::fmt::Debug for Quantity<T, D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} {}", self.value, D::symbol())
+    }
+}
+
+// same unit only, with the usual promotion of the numbers underneath
+impl<T, U, V, D> Add<Quantity<U, D>> for Quantity<T, D>
+where
+    T: Numerical + AsPrimitive<V> + Promote<U, Output = V>,
+    U: Numerical + AsPrimitive<V>,
+    V: Numerical + 'static,
+{
+    type Output = Quantity<V, D>;
+
+    fn add(self, rhs: Quantity<U, D>) -> Self::Output {
+        Quantity::new(self.value.as_() + rhs.value.as_())
+    }
+}
+
+impl<T, U, V, D> Sub<Quantity<U, D>> for Quantity<T, D>
+where
+    T: Numerical + AsPrimitive<V> + Promote<U, Output = V>,
+    U: Numerical + AsPrimitive<V>,
+    V: Numerical + 'static,
+{
+    type Output = Quantity<V, D>;
+
+    fn sub(self, rhs: Quantity<U, D>) -> Self::Output {
+        Quantity::new(self.value.as_() - rhs.value.as_())
+    }
+}
+
+impl<T, U, V, D1, D2> Mul<Quantity<U, D2>> for Quantity<T, D1>
+where
+    T: Numerical + AsPrimitive<V> + Promote<U, Output = V>,
+    U: Numerical + AsPrimitive<V>,
+    V: Numerical + 'static,
+    D1: DimMul<D2>,
+{
+    type Output = Quantity<V, D1::Output>;
+
+    fn mul(self, rhs: Quantity<U, D2>) -> Self::Output {
+        Quantity::new(self.value.as_() * rhs.value.as_())
+    }
+}
+
+impl<T, U, V, D1, D2> Div<Quantity<U, D2>> for Quantity<T, D1>
+where
+    T: Numerical + AsPrimitive<V> + Promote<U, Output = V>,
+    U: Numerical + AsPrimitive<V>,
+    V: Numerical + 'static,
+    D1: DimDiv<D2>,
+{
+    type Output = Quantity<V, D1::Output>;
+
+    fn div(self, rhs: Quantity<U, D2>) -> Self::Output {
+        Quantity::new(self.value.as_() / rhs.value.as_())
+    }
+}
+
+// a plain number scales without changing the unit
+impl<T, U, V, D> Mul<U> for Quantity<T, D>
+where
+    T: Numerical + AsPrimitive<V> + Promote<U, Output = V>,
+    U: Numerical + AsPrimitive<V>,
+    V: Numerical + 'static,
+{
+    type Output = Quantity<V, D>;
+
+    fn mul(self, rhs: U) -> Self::Output {
+        Quantity::new(self.value.as_() * rhs.as_())
+    }
+}
+
+impl<T, U, V, D> Div<U> for Quantity<T, D>
+where
+    T: Numerical + AsPrimitive<V> + Promote<U, Output = V>,
+    U: Numerical + AsPrimitive<V>,
+    V: Numerical + 'static,
+{
+    type Output = Quantity<V, D>;
+
+    fn div(self, rhs: U) -> Self::Output {
+        Quantity::new(self.value.as_() / rhs.as_())
+    }
+}
+
+
+
+// NVecs of quantities //
+
+impl<T: Numerical, const N: usize> NVec<T, N> {
+    // gives every component the unit D
+    pub fn with_units<D>(self) -> NVec<Quantity<T, D>, N> {
+        NVec { components: self.components.map(Quantity::new) }
+    }
+}
+
+impl<T: Numerical, D, const N: usize> NVec<Quantity<T, D>, N> {
+    pub fn new_with_units(components: [T; N]) -> Self {
+        NVec { components: components.map(Quantity::new) }
+    }
+
+    // strips the units back off
+    pub fn values(self) -> NVec<T, N> {
+        NVec::new(self.components.map(|q| q.value))
+    }
+}
+
+// same units only
+impl<T, U, V, D, const N: usize> Add<NVec<Quantity<U, D>, N>> for NVec<Quantity<T, D>, N>
+where
+    T: Numerical + AsPrimitive<V> + Promote<U, Output = V>,
+    U: Numerical + AsPrimitive<V>,
+    V: Numerical + 'static,
+{
+    type Output = NVec<Quantity<V, D>, N>;
+
+    fn add(self, rhs: NVec<Quantity<U, D>, N>) -> Self::Output {
+        (self.values() + rhs.values()).with_units()
+    }
+}
+
+impl<T, U, V, D, const N: usize> Sub<NVec<Quantity<U, D>, N>> for NVec<Quantity<T, D>, N>
+where
+    T: Numerical + AsPrimitive<V> + Promote<U, Output = V>,
+    U: Numerical + AsPrimitive<V>,
+    V: Numerical + 'static,
+{
+    type Output = NVec<Quantity<V, D>, N>;
+
+    fn sub(self, rhs: NVec<Quantity<U, D>, N>) -> Self::Output {
+        (self.values() - rhs.values()).with_units()
+    }
+}
+
+// dot product multiplies the units
+impl<T, U, V, D1, D2, const N: usize> Mul<NVec<Quantity<U, D2>, N>> for NVec<Quantity<T, D1>, N>
+where
+    T: Numerical + AsPrimitive<V> + Promote<U, Output = V>,
+    U: Numerical + AsPrimitive<V>,
+    V: Numerical + Default + 'static,
+    D1: DimMul<D2>,
+{
+    type Output = Quantity<V, D1::Output>;
+
+    fn mul(self, rhs: NVec<Quantity<U, D2>, N>) -> Self::Output {
+        Quantity::new(self.values() * rhs.values())
+    }
+}
+
+impl<T, U, V, D1, D2> CrossProduct<NVec<Quantity<U, D2>, 3>> for NVec<Quantity<T, D1>, 3>
+where
+    T: Numerical + AsPrimitive<V> + Promote<U, Output = V>,
+    U: Numerical + AsPrimitive<V>,
+    V: Numerical + 'static,
+    D1: DimMul<D2>,
+{
+    type Output = NVec<Quantity<V, D1::Output>, 3>;
+
+    fn cross(self, rhs: NVec<Quantity<U, D2>, 3>) -> Self::Output {
+        self.values().cross(rhs.values()).with_units()
+    }
+}
+
+// scaling by a quantity, e.g. a velocity times a time gives a displacement
+impl<T, U, V, D1, D2, const N: usize> Mul<Quantity<U, D2>> for NVec<Quantity<T, D1>, N>
+where
+    T: Numerical + AsPrimitive<V> + Promote<U, Output = V>,
+    U: Numerical + AsPrimitive<V>,
+    V: Numerical + 'static,
+    D1: DimMul<D2>,
+{
+    type Output = NVec<Quantity<V, D1::Output>, N>;
+
+    fn mul(self, rhs: Quantity<U, D2>) -> Self::Output {
+        (self.values() * rhs.value).with_units()
+    }
+}
+
+impl<T, U, V, D1, D2, const N: usize> Div<Quantity<U, D2>> for NVec<Quantity<T, D1>, N>
+where
+    T: Numerical + AsPrimitive<V> + Promote<U, Output = V>,
+    U: Numerical + AsPrimitive<V>,
+    V: Numerical + 'static,
+    D1: DimDiv<D2>,
+{
+    type Output = NVec<Quantity<V, D1::Output>, N>;
+
+    fn div(self, rhs: Quantity<U, D2>) -> Self::Output {
+        NVec { components: self.components.map(|c| Quantity::new(c.value.as_() / rhs.value.as_())) }
+    }
+}
+
+// a unitless NVec times a quantity takes on its unit, e.g. a direction times a distance
+impl<T, U, V, D, const N: usize> Mul<Quantity<U, D>> for NVec<T, N>
+where
+    T: Numerical + AsPrimitive<V> + Promote<U, Output = V>,
+    U: Numerical + AsPrimitive<V>,
+    V: Numerical + 'static,
+{
+    type Output = NVec<Quantity<V, D>, N>;
+
+    fn mul(self, rhs: Quantity<U, D>) -> Self::Output {
+        (self * rhs.value).with_units()
+    }
+}
+
+// a plain number scales without changing the unit
+impl<T, U, V, D, const N: usize> Mul<U> for NVec<Quantity<T, D>, N>
+where
+    T: Numerical + AsPrimitive<V> + Promote<U, Output = V>,
+    U: Numerical + AsPrimitive<V>,
+    V: Numerical + 'static,
+{
+    type Output = NVec<Quantity<V, D>, N>;
+
+    fn mul(self, rhs: U) -> Self::Output {
+        (self.values() * rhs).with_units()
+    }
+}
+
+// the length of a vector of lengths is a length
+impl<T, D, const N: usize> Magnitude for &NVec<Quantity<T, D>, N>
+where
+    T: Numerical,
+    f64: From<T>,
+{
+    type Output = Quantity<f64, D>;
+
+    fn magnitude(self) -> Self::Output {
+        Quantity::new(self.values().magnitude())
+    }
+
+    fn mag(self) -> Self::Output {
+        self.magnitude()
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_unit_test() {
+        println!("\n\nTesting for same-unit quantity ops\n\n");
+        let a: NVec<Meters<f64>, 3> = NVec::new_with_units([1.0, 2.0, 3.0]);
+        let b: NVec<Meters<f64>, 3> = NVec::new_with_units([4.0, -5.0, 6.0]);
+
+        assert_eq!((a + b).values(), NVec::new([5.0, -3.0, 9.0]));
+        assert_eq!((a - b).values(), NVec::new([-3.0, 7.0, -3.0]));
+        assert_eq!((a * 2.0).values(), NVec::new([2.0, 4.0, 6.0]));
+
+        let area: Quantity<f64, Area> = a * b;
+        assert_eq!(area.value, 12.0);
+
+        let cross: NVec<Quantity<f64, Area>, 3> = a.cross(b);
+        assert_eq!(cross.values(), NVec::new([27.0, 6.0, -13.0]));
+
+        let len: Meters<f64> = NVec::<Meters<i32>, 3>::new_with_units([3, 4, 0]).mag();
+        assert_eq!(len.value, 5.0);
+        assert_eq!(format!("{:?}", len), "5.0 m");
+    }
+
+    #[test]
+    fn dimension_test() {
+        println!("\n\nTesting for dimensional analysis\n\n");
+        let position: NVec<Meters<f64>, 3> = NVec::new_with_units([10.0, 0.0, 0.0]);
+        let displacement: NVec<Meters<f64>, 3> = NVec::new_with_units([4.0, 2.0, -8.0]);
+        let dt: Seconds<f64> = Quantity::new(2.0);
+
+        let velocity: NVec<MetersPerSecond<f64>, 3> = displacement / dt;
+        assert_eq!(velocity.values(), NVec::new([2.0, 1.0, -4.0]));
+
+        let acceleration: NVec<MetersPerSecondSquared<f64>, 3> = velocity / dt;
+        let mass: Kilograms<f64> = Quantity::new(3.0);
+        let force: NVec<Newtons<f64>, 3> = acceleration * mass;
+        assert_eq!(force.values(), NVec::new([3.0, 1.5, -6.0]));
+        assert_eq!(format!("{:?}", force.components[0]), "3.0 m kg s^-2");
+
+        let moved: NVec<Meters<f64>, 3> = position + velocity * dt;
+        assert_eq!(moved.values(), NVec::new([14.0, 2.0, -8.0]));
+
+        let work: Joules<f64> = force * displacement;
+        assert_eq!(work.value, 12.0 + 3.0 + 48.0);
+
+        // a bare direction times a distance picks up the unit, with promotion as usual
+        let step: NVec<Meters<f64>, 3> = NVec::new([1, 0, 0]) * Quantity::<f64, Length>::new(0.5);
+        assert_eq!(step.values(), NVec::new([0.5, 0.0, 0.0]));
+
+        let ratio: Quantity<f64, Dimensionless> = dt / Seconds::new(4.0);
+        assert_eq!(ratio.value, 0.5);
+        assert_eq!(<Velocity as Dimension>::symbol(), "m s^-1");
+    }
+}